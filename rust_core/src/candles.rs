@@ -0,0 +1,316 @@
+//! Candles Module - OHLCV Aggregation and Best-Bid/Ask History
+//!
+//! Subscribes to orderbook mid-price updates and aggregates them into OHLCV
+//! candles at multiple configurable resolutions per `token_id`, so strategies
+//! can query historical bars without an external database.
+
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::RwLock;
+
+/// Candle resolution, in seconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Resolution {
+    Sec1,
+    Min1,
+    Min5,
+    Hour1,
+}
+
+impl Resolution {
+    pub fn secs(self) -> u64 {
+        match self {
+            Resolution::Sec1 => 1,
+            Resolution::Min1 => 60,
+            Resolution::Min5 => 300,
+            Resolution::Hour1 => 3600,
+        }
+    }
+
+    pub fn bucket_start(self, ts: u64) -> u64 {
+        ts - (ts % self.secs())
+    }
+
+    pub fn from_str_pub(s: &str) -> Option<Self> {
+        match s {
+            "1s" => Some(Resolution::Sec1),
+            "1m" => Some(Resolution::Min1),
+            "5m" => Some(Resolution::Min5),
+            "1h" => Some(Resolution::Hour1),
+            _ => None,
+        }
+    }
+}
+
+/// A single OHLCV candle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[pyclass]
+pub struct Candle {
+    #[pyo3(get)]
+    pub start_ts: u64,
+    #[pyo3(get)]
+    pub open: f64,
+    #[pyo3(get)]
+    pub high: f64,
+    #[pyo3(get)]
+    pub low: f64,
+    #[pyo3(get)]
+    pub close: f64,
+    #[pyo3(get)]
+    pub volume: f64,
+}
+
+#[pymethods]
+impl Candle {
+    fn __repr__(&self) -> String {
+        format!(
+            "Candle(ts={}, o={:.4}, h={:.4}, l={:.4}, c={:.4}, v={})",
+            self.start_ts, self.open, self.high, self.low, self.close, self.volume
+        )
+    }
+}
+
+/// Maximum candles retained per resolution (ring buffer length)
+const DEFAULT_RING_CAPACITY: usize = 1_000;
+
+const RESOLUTIONS: [Resolution; 4] = [Resolution::Sec1, Resolution::Min1, Resolution::Min5, Resolution::Hour1];
+
+/// Per-token candle aggregator across all tracked resolutions
+#[derive(Debug)]
+struct TokenCandles {
+    series: HashMap<Resolution, VecDeque<Candle>>,
+    best_bid_series: VecDeque<(u64, f64)>,
+    best_ask_series: VecDeque<(u64, f64)>,
+    capacity: usize,
+}
+
+impl TokenCandles {
+    fn new(capacity: usize) -> Self {
+        let mut series = HashMap::new();
+        for r in RESOLUTIONS {
+            series.insert(r, VecDeque::with_capacity(capacity));
+        }
+        Self {
+            series,
+            best_bid_series: VecDeque::with_capacity(capacity),
+            best_ask_series: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    fn record_mid(&mut self, ts: u64, price: f64, volume: f64) {
+        for r in RESOLUTIONS {
+            let bucket = r.bucket_start(ts);
+            let ring = self.series.get_mut(&r).unwrap();
+
+            match ring.back_mut() {
+                Some(last) if last.start_ts == bucket => {
+                    last.high = last.high.max(price);
+                    last.low = last.low.min(price);
+                    last.close = price;
+                    last.volume += volume;
+                }
+                _ => {
+                    if ring.len() == self.capacity {
+                        ring.pop_front();
+                    }
+                    ring.push_back(Candle {
+                        start_ts: bucket,
+                        open: price,
+                        high: price,
+                        low: price,
+                        close: price,
+                        volume,
+                    });
+                }
+            }
+        }
+    }
+
+    fn record_quote(&mut self, ts: u64, best_bid: Option<f64>, best_ask: Option<f64>) {
+        if let Some(bid) = best_bid {
+            if self.best_bid_series.len() == self.capacity {
+                self.best_bid_series.pop_front();
+            }
+            self.best_bid_series.push_back((ts, bid));
+        }
+        if let Some(ask) = best_ask {
+            if self.best_ask_series.len() == self.capacity {
+                self.best_ask_series.pop_front();
+            }
+            self.best_ask_series.push_back((ts, ask));
+        }
+    }
+
+    fn candles_in_range(&self, resolution: Resolution, from_ts: u64, to_ts: u64) -> Vec<Candle> {
+        self.series
+            .get(&resolution)
+            .map(|ring| {
+                ring.iter()
+                    .filter(|c| c.start_ts >= from_ts && c.start_ts <= to_ts)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn high_low_volume_since(&self, since_ts: u64) -> (f64, f64, f64) {
+        let relevant: Vec<&Candle> = self
+            .series
+            .get(&Resolution::Min1)
+            .map(|ring| ring.iter().filter(|c| c.start_ts >= since_ts).collect())
+            .unwrap_or_default();
+
+        let high = relevant.iter().map(|c| c.high).fold(f64::MIN, f64::max);
+        let low = relevant.iter().map(|c| c.low).fold(f64::MAX, f64::min);
+        let volume: f64 = relevant.iter().map(|c| c.volume).sum();
+        (high, low, volume)
+    }
+}
+
+/// Manager aggregating OHLCV candles for every tracked token
+#[derive(Debug, Default)]
+pub struct CandleStore {
+    tokens: RwLock<HashMap<String, TokenCandles>>,
+}
+
+impl CandleStore {
+    pub fn new() -> Self {
+        Self { tokens: RwLock::new(HashMap::new()) }
+    }
+
+    /// Feed a mid-price observation (e.g. from `OrderbookManager::apply_delta`)
+    pub fn record_mid(&self, token_id: &str, ts: u64, price: f64, volume: f64) {
+        let mut tokens = self.tokens.write().unwrap();
+        tokens
+            .entry(token_id.to_string())
+            .or_insert_with(|| TokenCandles::new(DEFAULT_RING_CAPACITY))
+            .record_mid(ts, price, volume);
+    }
+
+    /// Feed a best-bid/best-ask observation
+    pub fn record_quote(&self, token_id: &str, ts: u64, best_bid: Option<f64>, best_ask: Option<f64>) {
+        let mut tokens = self.tokens.write().unwrap();
+        tokens
+            .entry(token_id.to_string())
+            .or_insert_with(|| TokenCandles::new(DEFAULT_RING_CAPACITY))
+            .record_quote(ts, best_bid, best_ask);
+    }
+
+    pub fn get_candles(&self, token_id: &str, resolution: Resolution, from_ts: u64, to_ts: u64) -> Vec<Candle> {
+        self.tokens
+            .read()
+            .unwrap()
+            .get(token_id)
+            .map(|t| t.candles_in_range(resolution, from_ts, to_ts))
+            .unwrap_or_default()
+    }
+
+    /// High/low/volume over the last `window_secs` up to `now_ts`
+    pub fn high_low_volume(&self, token_id: &str, now_ts: u64, window_secs: u64) -> (f64, f64, f64) {
+        let since = now_ts.saturating_sub(window_secs);
+        self.tokens
+            .read()
+            .unwrap()
+            .get(token_id)
+            .map(|t| t.high_low_volume_since(since))
+            .unwrap_or((0.0, 0.0, 0.0))
+    }
+}
+
+// ============ PyO3 Bindings ============
+
+#[pyclass]
+pub struct PyCandleStore {
+    inner: std::sync::Arc<CandleStore>,
+}
+
+#[pymethods]
+impl PyCandleStore {
+    #[new]
+    fn new() -> Self {
+        Self { inner: std::sync::Arc::new(CandleStore::new()) }
+    }
+
+    /// Record a mid-price observation for a token at a unix timestamp
+    fn record_mid(&self, token_id: &str, ts: u64, price: f64, volume: f64) {
+        self.inner.record_mid(token_id, ts, price, volume);
+    }
+
+    /// Record a best-bid/best-ask observation for a token
+    fn record_quote(&self, token_id: &str, ts: u64, best_bid: Option<f64>, best_ask: Option<f64>) {
+        self.inner.record_quote(token_id, ts, best_bid, best_ask);
+    }
+
+    /// Get candles for a token/resolution ("1s", "1m", "5m", "1h") as JSON
+    fn get_candles_json(&self, token_id: &str, resolution: &str, from_ts: u64, to_ts: u64) -> String {
+        let Some(res) = Resolution::from_str_pub(resolution) else {
+            return "[]".to_string();
+        };
+        serde_json::to_string(&self.inner.get_candles(token_id, res, from_ts, to_ts)).unwrap_or_default()
+    }
+
+    /// 24h high/low/volume for a token, anchored at `now_ts`
+    fn high_low_volume_24h(&self, token_id: &str, now_ts: u64) -> (f64, f64, f64) {
+        self.inner.high_low_volume(token_id, now_ts, 86_400)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_mid_aggregates_ohlc_within_one_bucket() {
+        let store = CandleStore::new();
+        store.record_mid("tok", 0, 10.0, 1.0);
+        store.record_mid("tok", 30, 12.0, 2.0);
+        store.record_mid("tok", 59, 9.0, 3.0);
+
+        let candles = store.get_candles("tok", Resolution::Min1, 0, 60);
+        assert_eq!(candles.len(), 1);
+        let c = &candles[0];
+        assert_eq!(c.open, 10.0);
+        assert_eq!(c.high, 12.0);
+        assert_eq!(c.low, 9.0);
+        assert_eq!(c.close, 9.0);
+        assert_eq!(c.volume, 6.0);
+    }
+
+    #[test]
+    fn record_mid_opens_a_new_candle_once_the_bucket_rolls_over() {
+        let store = CandleStore::new();
+        store.record_mid("tok", 0, 10.0, 1.0);
+        store.record_mid("tok", 60, 11.0, 1.0);
+
+        let candles = store.get_candles("tok", Resolution::Min1, 0, 120);
+        assert_eq!(candles.len(), 2);
+        assert_eq!(candles[0].start_ts, 0);
+        assert_eq!(candles[1].start_ts, 60);
+    }
+
+    #[test]
+    fn high_low_volume_only_counts_candles_within_the_window() {
+        let store = CandleStore::new();
+        store.record_mid("tok", 0, 100.0, 5.0);
+        store.record_mid("tok", 200_000, 1.0, 1.0);
+
+        let (high, low, volume) = store.high_low_volume("tok", 200_000, 86_400);
+        assert_eq!(high, 1.0);
+        assert_eq!(low, 1.0);
+        assert_eq!(volume, 1.0);
+    }
+
+    #[test]
+    fn get_candles_for_unknown_token_is_empty() {
+        let store = CandleStore::new();
+        assert!(store.get_candles("nope", Resolution::Min1, 0, 100).is_empty());
+    }
+
+    #[test]
+    fn resolution_bucket_start_rounds_down_to_the_resolution_boundary() {
+        assert_eq!(Resolution::Min1.bucket_start(125), 120);
+        assert_eq!(Resolution::Hour1.bucket_start(3_601), 3_600);
+    }
+}
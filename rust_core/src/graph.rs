@@ -1,14 +1,73 @@
 //! Graph Module - Bot 1: The Correlation Scanner
-//! 
+//!
 //! A Directed Acyclic Graph (DAG) detector for market correlations.
-//! Detects Monotonicity Violations: P(Child) cannot be < P(Parent) when correlation=1.0
-//! 
-//! Example: If "Trump wins PA" implies "Trump wins Election" with correlation 1.0,
-//! then P(Election) >= P(PA) must hold.
+//!
+//! An edge's `correlation` is the conditional probability `r = P(child |
+//! parent)`. Fréchet-Hoeffding bounds then pin the child's feasible price
+//! to `[r*pp, r*pp + (1-pp)]` given `pp = P(parent)`: below the lower bound
+//! the child is underpriced, above the upper bound it's overpriced relative
+//! to the probability mass left over when the parent doesn't occur. At
+//! `r=1.0` the upper bound collapses to 1.0, recovering the simple
+//! monotonicity rule (child can't be < parent).
+//!
+//! Example: If "Trump wins PA" implies "Trump wins Election" with r=1.0,
+//! then P(Election) must be >= P(PA).
+//!
+//! `scan` checks more than direct edges: it computes the full transitive
+//! closure (topo-sorted via Kahn's algorithm, then propagated ancestor by
+//! ancestor) so a chain like A->B->C also implies a bound between A and C
+//! (using the best combined correlation over every path from A to C), not
+//! just the single-hop constraints.
 
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+
+/// Errors from validating a `CorrelationDAG`
+#[derive(Debug, thiserror::Error)]
+pub enum CycleError {
+    #[error("graph is not a DAG: cycle among {0:?}")]
+    Cycle(Vec<String>),
+}
+
+/// A declarative rule in the correlation rule DSL. Tagged by `type` in JSON
+/// so a rule set can be written and version-controlled as config instead of
+/// imperative `add_edge`/`add_partition` calls from Python.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type")]
+pub enum Rule {
+    /// `parent` implies `child` with the given correlation strength.
+    Implies {
+        parent: String,
+        child: String,
+        correlation: f64,
+    },
+    /// A mutually-exclusive outcome group whose prices must sum to 1.0.
+    Partition { markets: Vec<String> },
+    /// `consequent` requires every market in `antecedents` to hold.
+    AllOf {
+        antecedents: Vec<String>,
+        consequent: String,
+    },
+    /// Any single market in `antecedents` is sufficient for `consequent`.
+    AnyOf {
+        antecedents: Vec<String>,
+        consequent: String,
+    },
+    /// `market` is negated by an (unspecified) related condition. Parsed for
+    /// forward compatibility, but the edge/partition model below only
+    /// expresses monotone implication, not negation, so lowering it fails.
+    Not { market: String },
+}
+
+/// Errors from loading a declarative rule set
+#[derive(Debug, thiserror::Error)]
+pub enum RuleError {
+    #[error("invalid rule set JSON: {0}")]
+    InvalidJson(String),
+    #[error("rule not supported by the current edge/partition model: {0:?}")]
+    Unsupported(Rule),
+}
 
 /// A node in the correlation graph (represents a market)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,25 +109,69 @@ pub struct Violation {
     pub mispricing_bps: f64,
     #[pyo3(get)]
     pub action: String,
+    /// The resolved chain of market ids from ancestor to descendant, e.g.
+    /// `["trump-wins-pa", "trump-wins-swing", "trump-wins-election"]` for a
+    /// two-hop transitive violation. A direct edge has exactly two entries.
+    #[pyo3(get)]
+    pub path: Vec<String>,
 }
 
 #[pymethods]
 impl Violation {
     fn __repr__(&self) -> String {
         format!(
-            "Violation({} -> {}: P({:.2}) < P({:.2}), mispricing={}bps)",
+            "Violation({} -> {}: {}, mispricing={}bps)",
             self.parent_id, self.child_id,
-            self.child_price, self.parent_price,
+            self.action,
             self.mispricing_bps as i64
         )
     }
 }
 
+/// A mutually-exclusive outcome group whose member prices must sum to 1.0,
+/// e.g. "Candidate A/B/C wins" in a single-winner race. Complements `Edge`:
+/// an edge expresses a pairwise implication, a partition expresses
+/// exhaustive, disjoint coverage that the edge model can't.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Partition {
+    pub market_ids: Vec<String>,
+}
+
+/// A sum-to-one arbitrage opportunity detected across a mutually-exclusive
+/// partition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[pyclass]
+pub struct PartitionViolation {
+    #[pyo3(get)]
+    pub market_ids: Vec<String>,
+    #[pyo3(get)]
+    pub sum_price: f64,
+    #[pyo3(get)]
+    pub deviation_bps: f64,
+    #[pyo3(get)]
+    pub action: String,
+}
+
+#[pymethods]
+impl PartitionViolation {
+    fn __repr__(&self) -> String {
+        format!(
+            "PartitionViolation({:?}: sum={:.4}, action={}, deviation={}bps)",
+            self.market_ids, self.sum_price, self.action, self.deviation_bps as i64
+        )
+    }
+}
+
+/// Default tolerance (in decimal, not bps) before a partition sum deviation
+/// is treated as a real opportunity rather than quoting noise.
+const DEFAULT_PARTITION_TOL: f64 = 0.01;
+
 /// The Correlation Scanner DAG
 #[derive(Debug, Default)]
 pub struct CorrelationDAG {
     nodes: HashMap<String, Node>,
     edges: Vec<Edge>,
+    partitions: Vec<Partition>,
 }
 
 impl CorrelationDAG {
@@ -94,43 +197,307 @@ impl CorrelationDAG {
         });
     }
 
-    /// Scan for monotonicity violations given current prices
-    /// Rule: P(Child) >= P(Parent) * correlation
-    /// If P(Child) < P(Parent) and correlation=1.0, it's a violation
-    pub fn scan(&self, prices: &HashMap<String, f64>) -> Vec<Violation> {
+    /// Add a mutually-exclusive outcome partition: its member prices must
+    /// sum to 1.0 (e.g. all candidates in a single-winner race).
+    pub fn add_partition(&mut self, market_ids: &[String]) {
+        self.partitions.push(Partition {
+            market_ids: market_ids.to_vec(),
+        });
+    }
+
+    /// Scan mutually-exclusive partitions for sum-to-one arbitrage: prices
+    /// summing to materially less than 1.0 mean buying every outcome locks
+    /// in a guaranteed $1 payoff for less than $1 (`BUY ALL`); materially
+    /// more than 1.0 means selling every outcome locks in the same edge in
+    /// reverse (`SELL ALL`). Partitions with a missing price are skipped.
+    pub fn scan_partitions(&self, prices: &HashMap<String, f64>, tol: f64) -> Vec<PartitionViolation> {
         let mut violations = Vec::new();
 
+        for partition in &self.partitions {
+            let mut sum = 0.0;
+            let mut complete = true;
+            for id in &partition.market_ids {
+                match prices.get(id) {
+                    Some(&p) => sum += p,
+                    None => {
+                        complete = false;
+                        break;
+                    }
+                }
+            }
+            if !complete || sum <= 0.0 {
+                continue;
+            }
+
+            if sum < 1.0 - tol {
+                violations.push(PartitionViolation {
+                    market_ids: partition.market_ids.clone(),
+                    sum_price: sum,
+                    deviation_bps: ((1.0 - sum) / sum) * 10_000.0,
+                    action: "BUY ALL".to_string(),
+                });
+            } else if sum > 1.0 + tol {
+                violations.push(PartitionViolation {
+                    market_ids: partition.market_ids.clone(),
+                    sum_price: sum,
+                    deviation_bps: ((sum - 1.0) / sum) * 10_000.0,
+                    action: "SELL ALL".to_string(),
+                });
+            }
+        }
+
+        violations
+    }
+
+    /// Lower a parsed rule set into `edges`/`partitions`. `AllOf` becomes one
+    /// implication edge per antecedent (consequent implies each antecedent,
+    /// since all of them must hold for the consequent to); `AnyOf` becomes
+    /// one implication edge per antecedent in the other direction (any one
+    /// antecedent already implies the consequent). `Not` has no lossless
+    /// lowering onto this model and is rejected.
+    pub fn load_rules(&mut self, rules: Vec<Rule>) -> Result<(), RuleError> {
+        for rule in rules {
+            match rule {
+                Rule::Implies { parent, child, correlation } => {
+                    self.add_edge(&parent, &child, correlation);
+                }
+                Rule::Partition { markets } => {
+                    self.add_partition(&markets);
+                }
+                Rule::AllOf { antecedents, consequent } => {
+                    for antecedent in &antecedents {
+                        self.add_edge(&consequent, antecedent, 1.0);
+                    }
+                }
+                Rule::AnyOf { antecedents, consequent } => {
+                    for antecedent in &antecedents {
+                        self.add_edge(antecedent, &consequent, 1.0);
+                    }
+                }
+                rule @ Rule::Not { .. } => return Err(RuleError::Unsupported(rule)),
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse and load a rule set from a JSON array of tagged rule objects in
+    /// one call, e.g. `[{"type": "Implies", "parent": "a", "child": "b",
+    /// "correlation": 1.0}]`.
+    pub fn load_rules_json(&mut self, rules_json: &str) -> Result<(), RuleError> {
+        let rules: Vec<Rule> =
+            serde_json::from_str(rules_json).map_err(|e| RuleError::InvalidJson(e.to_string()))?;
+        self.load_rules(rules)
+    }
+
+    /// Kahn's algorithm: repeatedly pop a zero-in-degree node and decrement
+    /// its children's in-degree. Returns the emitted topological order plus
+    /// whatever nodes never hit zero in-degree — non-empty only when the
+    /// graph has a cycle.
+    fn kahn(&self) -> (Vec<String>, Vec<String>) {
+        let mut out_adj: HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut in_degree: HashMap<String, usize> = HashMap::new();
+
         for edge in &self.edges {
-            let parent_price = prices.get(&edge.parent_id).copied();
-            let child_price = prices.get(&edge.child_id).copied();
-
-            if let (Some(pp), Some(cp)) = (parent_price, child_price) {
-                // Expected minimum child price based on correlation
-                let expected_min = pp * edge.correlation;
-                
-                // VIOLATION: Child is priced LOWER than it should be
-                // If parent=0.70, child=0.50, and correlation=1.0, child is underpriced
-                if cp < expected_min {
-                    let mispricing = expected_min - cp;
-                    let mispricing_bps = (mispricing / cp) * 10_000.0;
-                    
-                    violations.push(Violation {
-                        parent_id: edge.parent_id.clone(),
-                        child_id: edge.child_id.clone(),
-                        parent_price: pp,
-                        child_price: cp,
-                        correlation: edge.correlation,
-                        mispricing,
-                        mispricing_bps,
-                        action: format!("BUY {} (underpriced) / SELL {} (overpriced)", 
-                            edge.child_id, edge.parent_id),
-                    });
+            out_adj.entry(&edge.parent_id).or_default().push(&edge.child_id);
+            in_degree.entry(edge.parent_id.clone()).or_insert(0);
+            *in_degree.entry(edge.child_id.clone()).or_insert(0) += 1;
+        }
+        for id in self.nodes.keys() {
+            in_degree.entry(id.clone()).or_insert(0);
+        }
+
+        let mut queue: VecDeque<String> =
+            in_degree.iter().filter(|(_, &d)| d == 0).map(|(k, _)| k.clone()).collect();
+        let mut order = Vec::with_capacity(in_degree.len());
+
+        while let Some(n) = queue.pop_front() {
+            if let Some(children) = out_adj.get(n.as_str()) {
+                for &child in children {
+                    let d = in_degree.get_mut(child).unwrap();
+                    *d -= 1;
+                    if *d == 0 {
+                        queue.push_back(child.to_string());
+                    }
+                }
+            }
+            order.push(n);
+        }
+
+        let remaining: Vec<String> = in_degree
+            .keys()
+            .filter(|id| !order.contains(id))
+            .cloned()
+            .collect();
+        (order, remaining)
+    }
+
+    /// Topological order over every node touched by an edge. A cycle (which
+    /// shouldn't occur in a well-formed correlation DAG) leaves some nodes
+    /// un-popped by Kahn's algorithm; those are appended in arbitrary order
+    /// rather than panicking, so transitive reasoning still terminates.
+    /// Use [`CorrelationDAG::validate`] to detect and reject cycles instead.
+    fn topo_order(&self) -> Vec<String> {
+        let (mut order, remaining) = self.kahn();
+        order.extend(remaining);
+        order
+    }
+
+    /// Validate that the graph is actually a DAG. Returns the topological
+    /// order on success, or a `CycleError` listing the node(s) that never
+    /// reached zero in-degree (i.e. the cycle) on failure.
+    pub fn validate(&self) -> Result<Vec<String>, CycleError> {
+        let (order, remaining) = self.kahn();
+        if remaining.is_empty() {
+            Ok(order)
+        } else {
+            Err(CycleError::Cycle(remaining))
+        }
+    }
+
+    /// For every node reachable from an ancestor, the best (highest-product)
+    /// combined correlation over any path, plus the path itself for
+    /// reporting. Computed by walking the topo order and propagating each
+    /// node's known ancestors (plus itself) across its outgoing edges, so
+    /// every ancestor is fully resolved before it's used downstream.
+    fn transitive_closure(&self) -> HashMap<String, HashMap<String, (f64, Vec<String>)>> {
+        let mut out_adj: HashMap<&str, Vec<(&str, f64)>> = HashMap::new();
+        for edge in &self.edges {
+            out_adj.entry(&edge.parent_id).or_default().push((&edge.child_id, edge.correlation));
+        }
+
+        let order = self.topo_order();
+        let mut reach: HashMap<String, HashMap<String, (f64, Vec<String>)>> = HashMap::new();
+
+        for n in &order {
+            let Some(children) = out_adj.get(n.as_str()) else { continue };
+            for &(child, edge_corr) in children {
+                let mut candidates: Vec<(String, f64, Vec<String>)> =
+                    vec![(n.clone(), edge_corr, vec![n.clone(), child.to_string()])];
+                if let Some(ancestors) = reach.get(n) {
+                    for (anc, (corr, path)) in ancestors {
+                        let mut new_path = path.clone();
+                        new_path.push(child.to_string());
+                        candidates.push((anc.clone(), corr * edge_corr, new_path));
+                    }
+                }
+
+                let child_entry = reach.entry(child.to_string()).or_default();
+                for (anc, combined, path) in candidates {
+                    child_entry
+                        .entry(anc)
+                        .and_modify(|existing| {
+                            if combined > existing.0 {
+                                *existing = (combined, path.clone());
+                            }
+                        })
+                        .or_insert((combined, path));
+                }
+            }
+        }
+
+        reach
+    }
+
+    /// Check the Fréchet-Hoeffding bounds implied by treating `r` as the
+    /// conditional probability `P(child | parent)`: the child's price must
+    /// sit in `[r*pp, r*pp + (1-pp)]`. Below the lower bound the child is
+    /// underpriced (buy child / sell parent); above the upper bound the
+    /// child is overpriced relative to the probability mass available when
+    /// the parent doesn't occur (sell child / buy parent). Returns `None`
+    /// when `cp` is within bounds.
+    fn frechet_violation(
+        ancestor: &str,
+        descendant: &str,
+        pp: f64,
+        cp: f64,
+        r: f64,
+        path: Vec<String>,
+    ) -> Option<Violation> {
+        let lower = r * pp;
+        let upper = r * pp + (1.0 - pp);
+
+        let (mispricing, action) = if cp < lower {
+            (
+                lower - cp,
+                format!("child underpriced: BUY {} / SELL {}", descendant, ancestor),
+            )
+        } else if cp > upper {
+            (
+                cp - upper,
+                format!("child overpriced: SELL {} / BUY {}", descendant, ancestor),
+            )
+        } else {
+            return None;
+        };
+
+        Some(Violation {
+            parent_id: ancestor.to_string(),
+            child_id: descendant.to_string(),
+            parent_price: pp,
+            child_price: cp,
+            correlation: r,
+            mispricing,
+            mispricing_bps: (mispricing / cp) * 10_000.0,
+            action,
+            path,
+        })
+    }
+
+    /// Scan for Fréchet-Hoeffding bound violations given current prices,
+    /// across every (ancestor, descendant) pair in the transitive closure,
+    /// not just direct edges. Validates the graph first: a cyclic edge set
+    /// has no well-defined topological order, so transitive propagation is
+    /// skipped and only direct edges are checked in that case.
+    pub fn scan(&self, prices: &HashMap<String, f64>) -> Vec<Violation> {
+        if self.validate().is_err() {
+            return self.scan_direct_edges(prices);
+        }
+
+        let mut violations = Vec::new();
+
+        for (descendant, ancestors) in &self.transitive_closure() {
+            let Some(&cp) = prices.get(descendant) else { continue };
+
+            for (ancestor, (combined_correlation, path)) in ancestors {
+                let Some(&pp) = prices.get(ancestor) else { continue };
+
+                if let Some(v) =
+                    Self::frechet_violation(ancestor, descendant, pp, cp, *combined_correlation, path.clone())
+                {
+                    violations.push(v);
                 }
             }
         }
 
         violations
     }
+
+    /// Direct-edge-only fallback used when the graph contains a cycle and
+    /// has no well-defined topological order to propagate through.
+    fn scan_direct_edges(&self, prices: &HashMap<String, f64>) -> Vec<Violation> {
+        let mut violations = Vec::new();
+
+        for edge in &self.edges {
+            let (Some(&pp), Some(&cp)) =
+                (prices.get(&edge.parent_id), prices.get(&edge.child_id))
+            else {
+                continue;
+            };
+
+            if let Some(v) = Self::frechet_violation(
+                &edge.parent_id,
+                &edge.child_id,
+                pp,
+                cp,
+                edge.correlation,
+                vec![edge.parent_id.clone(), edge.child_id.clone()],
+            ) {
+                violations.push(v);
+            }
+        }
+
+        violations
+    }
 }
 
 // ============ PyO3 Bindings ============
@@ -174,6 +541,20 @@ impl Graph {
             .collect()
     }
 
+    /// Register a mutually-exclusive outcome group whose prices must sum to
+    /// 1.0 (e.g. all candidates in a single-winner race).
+    fn add_partition(&mut self, market_ids: Vec<String>) {
+        self.dag.add_partition(&market_ids);
+    }
+
+    /// Scan registered partitions for sum-to-one arbitrage
+    /// prices: list of (market_id, price) tuples
+    #[pyo3(signature = (prices, tol=DEFAULT_PARTITION_TOL))]
+    fn scan_partitions(&self, prices: Vec<(String, f64)>, tol: f64) -> Vec<PartitionViolation> {
+        let price_map: HashMap<String, f64> = prices.into_iter().collect();
+        self.dag.scan_partitions(&price_map, tol)
+    }
+
     /// Scan with JSON string input
     fn scan_json(&self, prices_json: &str) -> PyResult<Vec<Violation>> {
         let prices: HashMap<String, f64> = serde_json::from_str(prices_json)
@@ -184,6 +565,26 @@ impl Graph {
             .collect())
     }
 
+    /// Load a declarative rule set (a JSON array of tagged rule objects)
+    /// in one call instead of building the graph edge-by-edge from Python.
+    /// Supported tags: `Implies`, `Partition`, `AllOf`, `AnyOf`. `Not` is
+    /// parsed but has no lowering onto this edge model and raises
+    /// `ValueError`.
+    fn load_rules_json(&mut self, rules_json: &str) -> PyResult<()> {
+        self.dag
+            .load_rules_json(rules_json)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    }
+
+    /// Validate that the added edges form an actual DAG. Returns the
+    /// topological order of market ids on success, or raises `ValueError`
+    /// naming the cycle members on failure.
+    fn validate(&self) -> PyResult<Vec<String>> {
+        self.dag
+            .validate()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    }
+
     /// Get number of edges in the graph
     fn edge_count(&self) -> usize {
         self.dag.edges.len()
@@ -228,12 +629,269 @@ mod tests {
     fn test_no_violation() {
         let mut dag = CorrelationDAG::new();
         dag.add_edge("trump-wins-pa", "trump-wins-election", 1.0);
-        
+
         let mut prices = HashMap::new();
         prices.insert("trump-wins-pa".to_string(), 0.50);
         prices.insert("trump-wins-election".to_string(), 0.70); // Valid: child > parent
-        
+
+        let violations = dag.scan(&prices);
+        assert_eq!(violations.len(), 0);
+    }
+
+    #[test]
+    fn test_transitive_violation_across_two_hops() {
+        let mut dag = CorrelationDAG::new();
+        // A -> B -> C, no direct A -> C edge
+        dag.add_edge("a", "b", 1.0);
+        dag.add_edge("b", "c", 1.0);
+
+        let mut prices = HashMap::new();
+        prices.insert("a".to_string(), 0.70);
+        prices.insert("b".to_string(), 0.70);
+        prices.insert("c".to_string(), 0.50); // underpriced relative to A via the transitive chain
+
+        let violations = dag.scan(&prices);
+        // Both the direct B->C hop and the transitive A->C pair should fire.
+        assert_eq!(violations.len(), 2);
+        let transitive = violations
+            .iter()
+            .find(|v| v.parent_id == "a" && v.child_id == "c")
+            .expect("transitive a->c violation");
+        assert_eq!(transitive.path, vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+        assert!((transitive.correlation - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_transitive_closure_uses_best_combined_path() {
+        let mut dag = CorrelationDAG::new();
+        dag.add_edge("a", "b", 0.5);
+        dag.add_edge("b", "c", 0.5);
+        dag.add_edge("a", "c", 0.9); // direct edge beats the weaker two-hop path (0.25)
+
+        let mut prices = HashMap::new();
+        prices.insert("a".to_string(), 1.0);
+        prices.insert("b".to_string(), 1.0);
+        prices.insert("c".to_string(), 0.80); // below 1.0 * 0.9, above 1.0 * 0.25
+
+        let violations = dag.scan(&prices);
+        let ac = violations
+            .iter()
+            .find(|v| v.parent_id == "a" && v.child_id == "c")
+            .expect("a->c violation using the stronger direct edge");
+        assert_eq!(ac.path, vec!["a".to_string(), "c".to_string()]);
+        assert!((ac.correlation - 0.9).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_validate_accepts_a_dag() {
+        let mut dag = CorrelationDAG::new();
+        dag.add_edge("a", "b", 1.0);
+        dag.add_edge("b", "c", 1.0);
+
+        let order = dag.validate().expect("acyclic graph should validate");
+        let pos = |id: &str| order.iter().position(|n| n == id).unwrap();
+        assert!(pos("a") < pos("b"));
+        assert!(pos("b") < pos("c"));
+    }
+
+    #[test]
+    fn test_validate_rejects_a_cycle() {
+        let mut dag = CorrelationDAG::new();
+        dag.add_edge("a", "b", 1.0);
+        dag.add_edge("b", "c", 1.0);
+        dag.add_edge("c", "a", 1.0);
+
+        let err = dag.validate().unwrap_err();
+        match err {
+            CycleError::Cycle(members) => {
+                assert_eq!(members.len(), 3);
+                for id in ["a", "b", "c"] {
+                    assert!(members.contains(&id.to_string()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_scan_falls_back_to_direct_edges_on_cycle() {
+        let mut dag = CorrelationDAG::new();
+        dag.add_edge("a", "b", 1.0);
+        dag.add_edge("b", "c", 1.0);
+        dag.add_edge("c", "a", 1.0);
+
+        let mut prices = HashMap::new();
+        prices.insert("a".to_string(), 0.70);
+        prices.insert("b".to_string(), 0.50);
+        prices.insert("c".to_string(), 0.70);
+
+        // Still detects the direct a->b violation without looping forever
+        // despite the cycle making transitive propagation ill-defined.
         let violations = dag.scan(&prices);
+        assert!(violations.iter().any(|v| v.parent_id == "a" && v.child_id == "b"));
+    }
+
+    #[test]
+    fn test_scan_partitions_flags_underpriced_sum() {
+        let mut dag = CorrelationDAG::new();
+        dag.add_partition(&["candidate-a".to_string(), "candidate-b".to_string(), "candidate-c".to_string()]);
+
+        let mut prices = HashMap::new();
+        prices.insert("candidate-a".to_string(), 0.30);
+        prices.insert("candidate-b".to_string(), 0.30);
+        prices.insert("candidate-c".to_string(), 0.25); // sums to 0.85, well under 1 - tol
+
+        let violations = dag.scan_partitions(&prices, DEFAULT_PARTITION_TOL);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].action, "BUY ALL");
+        assert!((violations[0].sum_price - 0.85).abs() < 1e-9);
+        assert!(violations[0].deviation_bps > 0.0);
+    }
+
+    #[test]
+    fn test_scan_partitions_flags_overpriced_sum() {
+        let mut dag = CorrelationDAG::new();
+        dag.add_partition(&["candidate-a".to_string(), "candidate-b".to_string()]);
+
+        let mut prices = HashMap::new();
+        prices.insert("candidate-a".to_string(), 0.60);
+        prices.insert("candidate-b".to_string(), 0.55); // sums to 1.15, well over 1 + tol
+
+        let violations = dag.scan_partitions(&prices, DEFAULT_PARTITION_TOL);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].action, "SELL ALL");
+    }
+
+    #[test]
+    fn test_scan_partitions_ignores_noise_within_tolerance() {
+        let mut dag = CorrelationDAG::new();
+        dag.add_partition(&["candidate-a".to_string(), "candidate-b".to_string()]);
+
+        let mut prices = HashMap::new();
+        prices.insert("candidate-a".to_string(), 0.50);
+        prices.insert("candidate-b".to_string(), 0.495); // sums to 0.995, inside default tol
+
+        let violations = dag.scan_partitions(&prices, DEFAULT_PARTITION_TOL);
         assert_eq!(violations.len(), 0);
     }
+
+    #[test]
+    fn test_scan_partitions_skips_incomplete_prices() {
+        let mut dag = CorrelationDAG::new();
+        dag.add_partition(&["candidate-a".to_string(), "candidate-b".to_string()]);
+
+        let mut prices = HashMap::new();
+        prices.insert("candidate-a".to_string(), 0.30); // candidate-b missing
+
+        let violations = dag.scan_partitions(&prices, DEFAULT_PARTITION_TOL);
+        assert_eq!(violations.len(), 0);
+    }
+
+    #[test]
+    fn test_load_rules_json_lowers_implies_and_partition() {
+        let mut dag = CorrelationDAG::new();
+        let json = r#"[
+            {"type": "Implies", "parent": "trump-wins-pa", "child": "trump-wins-election", "correlation": 1.0},
+            {"type": "Partition", "markets": ["candidate-a", "candidate-b", "candidate-c"]}
+        ]"#;
+        dag.load_rules_json(json).expect("valid rule set should load");
+
+        assert_eq!(dag.edges.len(), 1);
+        assert_eq!(dag.partitions.len(), 1);
+    }
+
+    #[test]
+    fn test_load_rules_json_lowers_all_of_to_n_edges() {
+        let mut dag = CorrelationDAG::new();
+        let json = r#"[
+            {"type": "AllOf", "antecedents": ["senate-control", "house-control"], "consequent": "trifecta"}
+        ]"#;
+        dag.load_rules_json(json).expect("valid rule set should load");
+
+        assert_eq!(dag.edges.len(), 2);
+        assert!(dag.edges.iter().all(|e| e.parent_id == "trifecta"));
+
+        // The combined check falls out of scan()'s existing multi-ancestor
+        // handling: trifecta can't be priced above either antecedent.
+        let mut prices = HashMap::new();
+        prices.insert("senate-control".to_string(), 0.60);
+        prices.insert("house-control".to_string(), 0.55);
+        prices.insert("trifecta".to_string(), 0.70); // overpriced vs both antecedents
+
+        let violations = dag.scan(&prices);
+        assert_eq!(violations.len(), 2);
+    }
+
+    #[test]
+    fn test_load_rules_json_lowers_any_of_to_n_edges() {
+        let mut dag = CorrelationDAG::new();
+        let json = r#"[
+            {"type": "AnyOf", "antecedents": ["pa-flips", "mi-flips"], "consequent": "seat-change"}
+        ]"#;
+        dag.load_rules_json(json).expect("valid rule set should load");
+
+        assert_eq!(dag.edges.len(), 2);
+        assert!(dag.edges.iter().all(|e| e.child_id == "seat-change"));
+    }
+
+    #[test]
+    fn test_load_rules_json_rejects_not() {
+        let mut dag = CorrelationDAG::new();
+        let json = r#"[{"type": "Not", "market": "trump-wins-pa"}]"#;
+
+        let err = dag.load_rules_json(json).unwrap_err();
+        assert!(matches!(err, RuleError::Unsupported(Rule::Not { .. })));
+    }
+
+    #[test]
+    fn test_load_rules_json_rejects_malformed_json() {
+        let mut dag = CorrelationDAG::new();
+        let err = dag.load_rules_json("not valid json").unwrap_err();
+        assert!(matches!(err, RuleError::InvalidJson(_)));
+    }
+
+    #[test]
+    fn test_frechet_upper_bound_catches_overpriced_child() {
+        let mut dag = CorrelationDAG::new();
+        // r = P(child | parent) = 0.5: child can be priced at most
+        // 0.5*pp + (1 - pp) before it's overpriced relative to the mass
+        // available when the parent doesn't occur.
+        dag.add_edge("parent", "child", 0.5);
+
+        let mut prices = HashMap::new();
+        prices.insert("parent".to_string(), 0.40);
+        prices.insert("child".to_string(), 0.90); // upper bound = 0.5*0.4 + 0.6 = 0.80
+
+        let violations = dag.scan(&prices);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].action.contains("overpriced"));
+        assert!((violations[0].mispricing - 0.10).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_frechet_bounds_allow_valid_imperfect_correlation() {
+        let mut dag = CorrelationDAG::new();
+        dag.add_edge("parent", "child", 0.5);
+
+        let mut prices = HashMap::new();
+        prices.insert("parent".to_string(), 0.40);
+        prices.insert("child".to_string(), 0.50); // within [0.20, 0.80]
+
+        let violations = dag.scan(&prices);
+        assert_eq!(violations.len(), 0);
+    }
+
+    #[test]
+    fn test_frechet_lower_bound_still_catches_underpricing() {
+        let mut dag = CorrelationDAG::new();
+        dag.add_edge("parent", "child", 0.5);
+
+        let mut prices = HashMap::new();
+        prices.insert("parent".to_string(), 0.40);
+        prices.insert("child".to_string(), 0.10); // below lower bound = 0.5*0.4 = 0.20
+
+        let violations = dag.scan(&prices);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].action.contains("underpriced"));
+        assert!((violations[0].mispricing - 0.10).abs() < 1e-9);
+    }
 }
@@ -1,9 +1,12 @@
 //! Polymarket HFT - Rust Core
 //! The Complete 5-Bot Suite + Infrastructure
 
+pub mod candles;
 pub mod graph;
 pub mod negrisk;
 pub mod orderbook;
+pub mod portfolio;
+pub mod quoting;
 pub mod signer;
 pub mod vulture;
 
@@ -11,16 +14,33 @@ use pyo3::prelude::*;
 
 #[pymodule]
 fn rust_core(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    m.add_class::<candles::PyCandleStore>()?;
+    m.add_class::<candles::Candle>()?;
     m.add_class::<graph::Graph>()?;
     m.add_class::<graph::Violation>()?;
+    m.add_class::<graph::PartitionViolation>()?;
     m.add_class::<negrisk::NegRisk>()?;
     m.add_class::<negrisk::NegRiskConfig>()?;
     m.add_class::<negrisk::Opportunity>()?;
+    m.add_class::<negrisk::PartitionOpportunity>()?;
+    m.add_class::<negrisk::ArbLeg>()?;
+    m.add_class::<negrisk::SizedOpportunity>()?;
     m.add_class::<vulture::Vulture>()?;
     m.add_class::<vulture::VultureConfig>()?;
     m.add_class::<vulture::MarketOpportunity>()?;
+    m.add_class::<vulture::backtest::BacktestResult>()?;
+    m.add_class::<vulture::backtest::DayBreakdown>()?;
+    m.add_class::<vulture::backtest::PyBacktester>()?;
+    m.add_class::<vulture::optimize::VultureOptimizer>()?;
+    m.add_class::<vulture::lifecycle::RestingOrder>()?;
+    m.add_class::<vulture::lifecycle::UnfilledDecision>()?;
+    m.add_class::<vulture::ladder::QuoteLevel>()?;
     m.add_class::<orderbook::PyOrderbook>()?;
     m.add_class::<orderbook::PyOrderbookManager>()?;
+    m.add_class::<portfolio::PyPortfolioHealth>()?;
+    m.add_class::<quoting::QuoteEngine>()?;
+    m.add_class::<quoting::QuoteEngineConfig>()?;
+    m.add_class::<quoting::Quote>()?;
     m.add_class::<signer::PySigner>()?;
     Ok(())
 }
@@ -11,10 +11,20 @@
 
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::orderbook::{Orderbook, Side};
 
 /// Default fee assumptions (in decimal, not bps)
 const DEFAULT_FEE: f64 = 0.02; // 2% round-trip fee assumption
 
+/// Errors from scanning a caller-specified BUY/SELL/KEEP partition
+#[derive(Debug, thiserror::Error)]
+pub enum NegRiskError {
+    #[error("invalid partition: {0}")]
+    InvalidPartition(String),
+}
+
 /// Type of NegRisk opportunity
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OpportunityType {
@@ -64,6 +74,96 @@ impl Opportunity {
     }
 }
 
+/// A single leg of a combinatorial partition arbitrage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[pyclass]
+pub struct ArbLeg {
+    #[pyo3(get)]
+    pub token_index: usize,
+    /// "BUY", "SELL", or "MINT"; no separate merge leg is emitted (an
+    /// all-"BUY" partition merges the complete set it buys)
+    #[pyo3(get)]
+    pub action: String,
+    #[pyo3(get)]
+    pub price: f64,
+}
+
+/// The winning partition arbitrage for a market, if any
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[pyclass]
+pub struct PartitionOpportunity {
+    #[pyo3(get)]
+    pub condition_id: String,
+    #[pyo3(get)]
+    pub legs: Vec<ArbLeg>,
+    #[pyo3(get)]
+    pub profit_gross: f64,
+    #[pyo3(get)]
+    pub profit_net: f64,
+    #[pyo3(get)]
+    pub profit_bps: f64,
+    #[pyo3(get)]
+    pub is_profitable: bool,
+}
+
+#[pymethods]
+impl PartitionOpportunity {
+    fn __repr__(&self) -> String {
+        format!(
+            "PartitionOpportunity({}: legs={}, profit={}bps)",
+            self.condition_id,
+            self.legs.len(),
+            self.profit_bps as i64
+        )
+    }
+}
+
+/// A mint-and-sell or buy-and-merge opportunity sized against real depth
+/// instead of the top-of-book price, via [`NegRisk::scan_sized`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[pyclass]
+pub struct SizedOpportunity {
+    #[pyo3(get)]
+    pub condition_id: String,
+    #[pyo3(get)]
+    pub opportunity_type: String,
+    /// Maximum shares fillable before `max_notional` or `min_profit_bps` is hit
+    #[pyo3(get)]
+    pub max_size: f64,
+    /// Volume-weighted average fill price per outcome, in leg order
+    #[pyo3(get)]
+    pub vwap_per_leg: Vec<f64>,
+    /// Realized net profit in dollars at `max_size`, after fees
+    #[pyo3(get)]
+    pub realized_profit_net: f64,
+    /// Realized net profit in basis points, averaged over `max_size`
+    #[pyo3(get)]
+    pub realized_profit_bps: f64,
+}
+
+#[pymethods]
+impl SizedOpportunity {
+    fn __repr__(&self) -> String {
+        format!(
+            "SizedOpportunity({}: size={:.2}, profit=${:.2} ({}bps))",
+            self.opportunity_type, self.max_size, self.realized_profit_net, self.realized_profit_bps as i64
+        )
+    }
+}
+
+/// One outcome's role in a candidate BUY/SELL/KEEP partition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Role {
+    Buy,
+    Sell,
+    Keep,
+}
+
+/// Above the enumeration threshold, only single-outcome-move partitions
+/// relative to the full-set baseline are evaluated (moving one outcome at a
+/// time from the baseline SELL/BUY-all partition into KEEP).
+const FULL_ENUMERATION_LIMIT: usize = 6;
+
 /// NegRisk Miner configuration
 #[derive(Debug, Clone)]
 #[pyclass]
@@ -77,6 +177,30 @@ pub struct NegRiskConfig {
     /// Maximum notional per trade
     #[pyo3(get, set)]
     pub max_notional: f64,
+    /// Minimum price-sum margin (decimal) required before a partition is
+    /// considered an opportunity, guarding against floating-point rounding
+    /// manufacturing phantom arbs
+    #[pyo3(get, set)]
+    pub partition_epsilon: f64,
+    /// When true, `scan_with_books` distrusts legs whose book has dislocated
+    /// from its stable (delay-limited) mid by more than `max_dislocation_bps`,
+    /// guarding against acting on a single transient thin quote
+    #[pyo3(get, set)]
+    pub use_stable_price: bool,
+    /// Maximum allowed deviation (bps) between a book's oracle mid and its
+    /// stable mid before a leg is treated as untrustworthy
+    #[pyo3(get, set)]
+    pub max_dislocation_bps: f64,
+    /// Market tick size: prices are quantized to this before `scan`/
+    /// `quick_check` sum them, so the mint/merge threshold test happens in
+    /// exact integer-tick space instead of drifting with repeated f64
+    /// addition across outcomes
+    #[pyo3(get, set)]
+    pub tick_size: f64,
+    /// Market lot size (minimum size increment), carried alongside
+    /// `tick_size` so callers can quantize sizes the same way they quantize price
+    #[pyo3(get, set)]
+    pub lot_size: f64,
 }
 
 #[pymethods]
@@ -87,10 +211,22 @@ impl NegRiskConfig {
             fee: DEFAULT_FEE,
             min_profit_bps: 10.0,
             max_notional: 1000.0,
+            partition_epsilon: 1e-6,
+            use_stable_price: false,
+            max_dislocation_bps: 200.0,
+            tick_size: 0.001,
+            lot_size: 1.0,
         }
     }
 }
 
+/// Round `price` to the nearest integer count of `tick_size` ticks, so
+/// summing several outcomes' prices happens as exact integer addition
+/// rather than accumulating f64 rounding error term by term.
+fn price_to_ticks(price: f64, tick_size: f64) -> i64 {
+    (price / tick_size).round() as i64
+}
+
 /// Bot 2: The NegRisk Miner
 #[pyclass]
 pub struct NegRisk {
@@ -127,16 +263,24 @@ impl NegRisk {
         bids: Vec<f64>,
         asks: Vec<f64>,
     ) -> Opportunity {
-        let sum_bids: f64 = bids.iter().sum();
-        let sum_asks: f64 = asks.iter().sum();
-        
+        let tick = self.config.tick_size;
+        let one_tick = price_to_ticks(1.0, tick);
+        let fee_ticks = price_to_ticks(self.config.fee, tick);
+
+        // Quantize every outcome's price to a whole number of ticks first,
+        // so the Σprice threshold test below is exact integer addition
+        // instead of accumulating f64 rounding error one outcome at a time.
+        let sum_bid_ticks: i64 = bids.iter().map(|&p| price_to_ticks(p, tick)).sum();
+        let sum_ask_ticks: i64 = asks.iter().map(|&p| price_to_ticks(p, tick)).sum();
+        let sum_bids: f64 = sum_bid_ticks as f64 * tick;
+        let sum_asks: f64 = sum_ask_ticks as f64 * tick;
+
         // Check for Mint-and-Sell: ΣBids > 1.0 + fee
-        let mint_threshold = 1.0 + self.config.fee;
-        if sum_bids > mint_threshold {
-            let profit_gross = sum_bids - 1.0;
+        if sum_bid_ticks > one_tick + fee_ticks {
+            let profit_gross = (sum_bid_ticks - one_tick) as f64 * tick;
             let profit_net = profit_gross - self.config.fee;
             let profit_bps = profit_net * 10_000.0;
-            
+
             return Opportunity {
                 opportunity_type: "MintAndSell".to_string(),
                 condition_id: condition_id.to_string(),
@@ -149,14 +293,13 @@ impl NegRisk {
                 is_profitable: profit_bps >= self.config.min_profit_bps,
             };
         }
-        
+
         // Check for Buy-and-Merge: ΣAsks < 1.0 - fee
-        let merge_threshold = 1.0 - self.config.fee;
-        if sum_asks < merge_threshold {
-            let profit_gross = 1.0 - sum_asks;
+        if sum_ask_ticks < one_tick - fee_ticks {
+            let profit_gross = (one_tick - sum_ask_ticks) as f64 * tick;
             let profit_net = profit_gross - self.config.fee;
             let profit_bps = profit_net * 10_000.0;
-            
+
             return Opportunity {
                 opportunity_type: "BuyAndMerge".to_string(),
                 condition_id: condition_id.to_string(),
@@ -169,7 +312,7 @@ impl NegRisk {
                 is_profitable: profit_bps >= self.config.min_profit_bps,
             };
         }
-        
+
         // No opportunity
         Opportunity {
             opportunity_type: "None".to_string(),
@@ -184,12 +327,17 @@ impl NegRisk {
         }
     }
 
-    /// Quick check for potential opportunity (fast filter)
+    /// Quick check for potential opportunity (fast filter). Sums prices in
+    /// integer-tick space, same as `scan`, so the filter agrees with the
+    /// full scan at the boundary instead of drifting due to f64 summation.
     fn quick_check(&self, bids: Vec<f64>, asks: Vec<f64>) -> bool {
-        let sum_bids: f64 = bids.iter().sum();
-        let sum_asks: f64 = asks.iter().sum();
-        
-        sum_bids > (1.0 + self.config.fee) || sum_asks < (1.0 - self.config.fee)
+        let tick = self.config.tick_size;
+        let one_tick = price_to_ticks(1.0, tick);
+        let fee_ticks = price_to_ticks(self.config.fee, tick);
+        let sum_bid_ticks: i64 = bids.iter().map(|&p| price_to_ticks(p, tick)).sum();
+        let sum_ask_ticks: i64 = asks.iter().map(|&p| price_to_ticks(p, tick)).sum();
+
+        sum_bid_ticks > one_tick + fee_ticks || sum_ask_ticks < one_tick - fee_ticks
     }
 
     /// Scan a binary market (2 outcomes: YES/NO)
@@ -204,6 +352,41 @@ impl NegRisk {
         self.scan(condition_id, vec![yes_bid, no_bid], vec![yes_ask, no_ask])
     }
 
+    /// Scan for combinatorial BUY/SELL/KEEP partition arbitrage across all
+    /// outcomes, beyond the simple full-set mint-and-sell / buy-and-merge cases
+    #[pyo3(name = "scan_partitions")]
+    fn py_scan_partitions(&self, condition_id: &str, bids: Vec<f64>, asks: Vec<f64>) -> Option<PartitionOpportunity> {
+        self.scan_partitions(condition_id, bids, asks)
+    }
+
+    /// Evaluate a caller-specified BUY/SELL/KEEP grouping (outcome indices
+    /// per group) instead of searching every partition. Raises `ValueError`
+    /// if the groups aren't pairwise disjoint and exhaustive over every
+    /// outcome, or if neither BUY nor SELL proposes a trade.
+    #[pyo3(name = "scan_given_partition")]
+    fn py_scan_given_partition(
+        &self,
+        condition_id: &str,
+        bids: Vec<f64>,
+        asks: Vec<f64>,
+        buy: Vec<usize>,
+        sell: Vec<usize>,
+        keep: Vec<usize>,
+    ) -> PyResult<Option<PartitionOpportunity>> {
+        self.scan_given_partition(condition_id, &bids, &asks, &buy, &sell, &keep)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    }
+
+    /// Depth-aware scan: `books` holds each outcome's resting levels as
+    /// `(price, size)` tuples sorted best-first. Walks every leg in lockstep
+    /// and returns the largest size fillable before `max_notional` or
+    /// `min_profit_bps` is hit, instead of a headline rate that only holds
+    /// for an infinitesimal lot.
+    #[pyo3(name = "scan_sized")]
+    fn py_scan_sized(&self, condition_id: &str, books: Vec<Vec<(f64, f64)>>) -> Option<SizedOpportunity> {
+        self.scan_sized(condition_id, &books)
+    }
+
     /// Get current config
     fn get_config(&self) -> NegRiskConfig {
         self.config.clone()
@@ -223,6 +406,427 @@ impl NegRisk {
     }
 }
 
+impl NegRisk {
+    /// Roles are constructed one-per-index so disjointness and full
+    /// coverage of `0..n` hold by construction; this just rejects the
+    /// degenerate partition that trades nothing.
+    fn validate_partition(roles: &[Role]) -> bool {
+        !roles.is_empty() && roles.iter().any(|r| *r != Role::Keep)
+    }
+
+    /// Strict validation for a caller-specified BUY/SELL/KEEP grouping,
+    /// ahead of any profit math: every index in `0..n` must appear in
+    /// exactly one group (pairwise disjoint, exhaustive), the market must
+    /// have at least 2 outcomes, and a trade must actually be proposed
+    /// (BUY or SELL non-empty).
+    fn validate_explicit_partition(
+        n: usize,
+        buy: &[usize],
+        sell: &[usize],
+        keep: &[usize],
+    ) -> Result<(), NegRiskError> {
+        if n < 2 {
+            return Err(NegRiskError::InvalidPartition(format!(
+                "market has {n} outcome(s), need at least 2"
+            )));
+        }
+
+        let mut seen = vec![false; n];
+        for &idx in buy.iter().chain(sell.iter()).chain(keep.iter()) {
+            if idx >= n {
+                return Err(NegRiskError::InvalidPartition(format!(
+                    "index {idx} is out of range for {n} outcomes"
+                )));
+            }
+            if seen[idx] {
+                return Err(NegRiskError::InvalidPartition(format!(
+                    "index {idx} appears in more than one group"
+                )));
+            }
+            seen[idx] = true;
+        }
+        if let Some(missing) = seen.iter().position(|covered| !covered) {
+            return Err(NegRiskError::InvalidPartition(format!(
+                "index {missing} is not covered by any group"
+            )));
+        }
+        if buy.is_empty() && sell.is_empty() {
+            return Err(NegRiskError::InvalidPartition(
+                "buy and sell groups are both empty - nothing to trade".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Build the per-index `Role` vector implied by a validated BUY/SELL/KEEP
+    /// grouping; any index not in `buy`/`sell` defaults to `Keep`.
+    fn roles_from_groups(n: usize, buy: &[usize], sell: &[usize]) -> Vec<Role> {
+        let mut roles = vec![Role::Keep; n];
+        for &i in buy {
+            roles[i] = Role::Buy;
+        }
+        for &i in sell {
+            roles[i] = Role::Sell;
+        }
+        roles
+    }
+
+    /// Evaluate one candidate partition against the unity constraint.
+    /// Whenever the SELL or KEEP group is non-empty, the strategy mints one
+    /// full set for $1 - which already hands over one share of *every*
+    /// outcome, BUY-labeled ones included, so there's no separate trade that
+    /// buys a leg the mint just produced for free. A BUY-labeled leg under a
+    /// mint therefore costs nothing and is simply marked to its bid like a
+    /// KEEP leg would be, collapsing to `profit_gross = Σbids - 1.0`
+    /// regardless of the SELL/KEEP/BUY split. A pure-BUY partition (no
+    /// SELL/KEEP at all) needs no mint: it buys every leg directly and
+    /// merges the complete set for its $1 redemption value,
+    /// `profit_gross = 1.0 - Σasks`, matching `scan_with_books`'s
+    /// `BuyAndMerge` case.
+    fn evaluate_partition(
+        &self,
+        condition_id: &str,
+        bids: &[f64],
+        asks: &[f64],
+        roles: &[Role],
+    ) -> Option<PartitionOpportunity> {
+        if !Self::validate_partition(roles) {
+            return None;
+        }
+
+        let needs_mint = roles.iter().any(|r| *r != Role::Buy);
+
+        let profit_gross = if needs_mint {
+            bids.iter().sum::<f64>() - 1.0
+        } else {
+            1.0 - asks.iter().sum::<f64>()
+        };
+
+        if profit_gross < self.config.partition_epsilon {
+            return None;
+        }
+
+        let profit_net = profit_gross - self.config.fee;
+        let profit_bps = profit_net * 10_000.0;
+
+        let mut legs: Vec<ArbLeg> = roles
+            .iter()
+            .enumerate()
+            .map(|(i, role)| {
+                if !needs_mint {
+                    return ArbLeg { token_index: i, action: "BUY".to_string(), price: asks[i] };
+                }
+                match role {
+                    // A mint already hands this leg over for free; BUY and
+                    // KEEP are indistinguishable once minting is in play.
+                    Role::Sell => ArbLeg { token_index: i, action: "SELL".to_string(), price: bids[i] },
+                    Role::Buy | Role::Keep => ArbLeg { token_index: i, action: "MINT".to_string(), price: bids[i] },
+                }
+            })
+            .collect();
+        legs.sort_by_key(|l| l.token_index);
+
+        Some(PartitionOpportunity {
+            condition_id: condition_id.to_string(),
+            legs,
+            profit_gross,
+            profit_net,
+            profit_bps,
+            is_profitable: profit_bps >= self.config.min_profit_bps,
+        })
+    }
+
+    /// Scan for combinatorial partition arbitrage: split the N mutually
+    /// exclusive, exhaustive outcomes into BUY, SELL, and KEEP groups and
+    /// return the most profitable valid partition found, if any.
+    ///
+    /// For `n <= FULL_ENUMERATION_LIMIT` every 3^n partition is evaluated;
+    /// above that, only single-outcome-move partitions relative to the
+    /// full-sell / full-buy baseline are tried.
+    pub fn scan_partitions(&self, condition_id: &str, bids: Vec<f64>, asks: Vec<f64>) -> Option<PartitionOpportunity> {
+        let n = bids.len();
+        if n == 0 || n != asks.len() {
+            return None;
+        }
+
+        let mut best: Option<PartitionOpportunity> = None;
+        let mut consider = |roles: Vec<Role>, best: &mut Option<PartitionOpportunity>| {
+            if let Some(opp) = self.evaluate_partition(condition_id, &bids, &asks, &roles) {
+                if best.as_ref().map(|b| opp.profit_gross > b.profit_gross).unwrap_or(true) {
+                    *best = Some(opp);
+                }
+            }
+        };
+
+        if n <= FULL_ENUMERATION_LIMIT {
+            let total = 3usize.pow(n as u32);
+            for mask in 0..total {
+                let mut m = mask;
+                let mut roles = Vec::with_capacity(n);
+                for _ in 0..n {
+                    roles.push(match m % 3 {
+                        0 => Role::Buy,
+                        1 => Role::Sell,
+                        _ => Role::Keep,
+                    });
+                    m /= 3;
+                }
+                consider(roles, &mut best);
+            }
+        } else {
+            // Baseline: sell everything (mint-and-sell) or buy everything
+            // (buy-and-merge), each with one outcome at a time moved to KEEP.
+            for baseline in [Role::Sell, Role::Buy] {
+                consider(vec![baseline; n], &mut best);
+                for k in 0..n {
+                    let mut roles = vec![baseline; n];
+                    roles[k] = Role::Keep;
+                    consider(roles, &mut best);
+                }
+            }
+        }
+
+        best
+    }
+
+    /// Evaluate a caller-specified BUY/SELL/KEEP grouping directly, rather
+    /// than searching every partition - useful for structured markets where
+    /// the grouping is known upfront (e.g. "which candidate wins", grouped
+    /// by party) instead of fully enumerated exclusive outcomes. Groups are
+    /// strictly validated (pairwise disjoint, exhaustive over `0..n`, at
+    /// least one of `buy`/`sell` non-empty) before any profit math runs.
+    pub fn scan_given_partition(
+        &self,
+        condition_id: &str,
+        bids: &[f64],
+        asks: &[f64],
+        buy: &[usize],
+        sell: &[usize],
+        keep: &[usize],
+    ) -> Result<Option<PartitionOpportunity>, NegRiskError> {
+        let n = bids.len();
+        if n != asks.len() {
+            return Err(NegRiskError::InvalidPartition(format!(
+                "bids has {n} outcomes but asks has {}",
+                asks.len()
+            )));
+        }
+        Self::validate_explicit_partition(n, buy, sell, keep)?;
+
+        let roles = Self::roles_from_groups(n, buy, sell);
+        Ok(self.evaluate_partition(condition_id, bids, asks, &roles))
+    }
+
+    /// True if any book's oracle mid has drifted from its stable mid by
+    /// more than `config.max_dislocation_bps`
+    fn any_book_dislocated(&self, books: &[Arc<Orderbook>]) -> bool {
+        books.iter().any(|b| {
+            match (b.oracle_mid(), b.stable_mid()) {
+                (Some(oracle), Some(stable)) if stable > 0.0 => {
+                    ((oracle - stable) / stable).abs() * 10_000.0 > self.config.max_dislocation_bps
+                }
+                _ => false,
+            }
+        })
+    }
+
+    /// Depth-aware scan: instead of assuming the full `size` fills at the
+    /// best bid/ask, walk each outcome's live book via `simulate_take` so
+    /// `profit_bps` reflects realized slippage across every leg.
+    ///
+    /// For MintAndSell, shares are minted and sold into each outcome's bids.
+    /// For BuyAndMerge, shares are bought from each outcome's asks and merged.
+    ///
+    /// When `config.use_stable_price` is set, any book whose oracle mid has
+    /// dislocated from its stable (delay-limited) mid by more than
+    /// `config.max_dislocation_bps` disqualifies the opportunity, since the
+    /// headline profit may only reflect a single transient thin quote.
+    pub fn scan_with_books(
+        &self,
+        condition_id: &str,
+        books: &[Arc<Orderbook>],
+        size: f64,
+    ) -> Opportunity {
+        if self.config.use_stable_price && self.any_book_dislocated(books) {
+            return Opportunity {
+                opportunity_type: "None".to_string(),
+                condition_id: condition_id.to_string(),
+                prices: Vec::new(),
+                sum_bids: 0.0,
+                sum_asks: 0.0,
+                profit_gross: 0.0,
+                profit_net: 0.0,
+                profit_bps: 0.0,
+                is_profitable: false,
+            };
+        }
+
+        let sell_fills: Vec<_> = books
+            .iter()
+            .map(|b| b.simulate_take(Side::Ask, size, None))
+            .collect();
+        let sum_sell_vwap: f64 = sell_fills.iter().map(|f| f.avg_price).sum();
+        let fully_sold = sell_fills.iter().all(|f| f.remaining <= 0.0);
+
+        let mint_threshold = 1.0 + self.config.fee;
+        if fully_sold && sum_sell_vwap > mint_threshold {
+            let profit_gross = sum_sell_vwap - 1.0;
+            let profit_net = profit_gross - self.config.fee;
+            let profit_bps = profit_net * 10_000.0;
+
+            return Opportunity {
+                opportunity_type: "MintAndSell".to_string(),
+                condition_id: condition_id.to_string(),
+                prices: sell_fills.iter().map(|f| f.avg_price).collect(),
+                sum_bids: sum_sell_vwap,
+                sum_asks: 0.0,
+                profit_gross,
+                profit_net,
+                profit_bps,
+                is_profitable: profit_bps >= self.config.min_profit_bps,
+            };
+        }
+
+        let buy_fills: Vec<_> = books
+            .iter()
+            .map(|b| b.simulate_take(Side::Bid, size, None))
+            .collect();
+        let sum_buy_vwap: f64 = buy_fills.iter().map(|f| f.avg_price).sum();
+        let fully_bought = buy_fills.iter().all(|f| f.remaining <= 0.0);
+
+        let merge_threshold = 1.0 - self.config.fee;
+        if fully_bought && sum_buy_vwap < merge_threshold {
+            let profit_gross = 1.0 - sum_buy_vwap;
+            let profit_net = profit_gross - self.config.fee;
+            let profit_bps = profit_net * 10_000.0;
+
+            return Opportunity {
+                opportunity_type: "BuyAndMerge".to_string(),
+                condition_id: condition_id.to_string(),
+                prices: buy_fills.iter().map(|f| f.avg_price).collect(),
+                sum_bids: 0.0,
+                sum_asks: sum_buy_vwap,
+                profit_gross,
+                profit_net,
+                profit_bps,
+                is_profitable: profit_bps >= self.config.min_profit_bps,
+            };
+        }
+
+        Opportunity {
+            opportunity_type: "None".to_string(),
+            condition_id: condition_id.to_string(),
+            prices: Vec::new(),
+            sum_bids: sum_sell_vwap,
+            sum_asks: sum_buy_vwap,
+            profit_gross: 0.0,
+            profit_net: 0.0,
+            profit_bps: 0.0,
+            is_profitable: false,
+        }
+    }
+
+    /// Walk every leg's book in lockstep, one price level at a time, and
+    /// return the largest size fillable before `max_notional` is consumed or
+    /// the next level's marginal edge drops below `min_profit_bps`.
+    ///
+    /// `books` holds each outcome's resting levels as `(price, size)`,
+    /// sorted best-first (descending for bids, ascending for asks).
+    /// `is_mint` selects the direction: `true` sells newly-minted shares into
+    /// every outcome's bids (MintAndSell), `false` buys every outcome's asks
+    /// to merge for $1 (BuyAndMerge). Returns `None` if no level clears
+    /// `min_profit_bps`.
+    fn walk_depth(&self, books: &[Vec<(f64, f64)>], is_mint: bool) -> Option<(f64, Vec<f64>, f64, f64)> {
+        let n = books.len();
+        let mut idx = vec![0usize; n];
+        let mut level_remaining: Vec<f64> = books.iter().map(|b| b.first().map_or(0.0, |l| l.1)).collect();
+        let mut leg_cost = vec![0.0_f64; n];
+        let mut leg_size = vec![0.0_f64; n];
+        let mut cum_size = 0.0_f64;
+        let mut cum_cost = 0.0_f64;
+
+        loop {
+            if (0..n).any(|i| idx[i] >= books[i].len()) {
+                break; // a leg's book ran dry before the others
+            }
+
+            let prices: Vec<f64> = (0..n).map(|i| books[i][idx[i]].0).collect();
+            let price_sum: f64 = prices.iter().sum();
+            let edge = if is_mint { price_sum - (1.0 + self.config.fee) } else { (1.0 - self.config.fee) - price_sum };
+            if edge * 10_000.0 < self.config.min_profit_bps {
+                break; // marginal edge of the next level no longer clears the threshold
+            }
+
+            let seg = level_remaining.iter().cloned().fold(f64::INFINITY, f64::min);
+            if seg <= 0.0 {
+                break;
+            }
+            let remaining_budget = self.config.max_notional - cum_cost;
+            if remaining_budget <= 0.0 {
+                break;
+            }
+            let take = if price_sum * seg > remaining_budget { remaining_budget / price_sum } else { seg };
+            if take <= 0.0 {
+                break;
+            }
+
+            cum_size += take;
+            cum_cost += price_sum * take;
+            for i in 0..n {
+                leg_cost[i] += prices[i] * take;
+                leg_size[i] += take;
+                level_remaining[i] -= take;
+                if level_remaining[i] <= 1e-9 {
+                    idx[i] += 1;
+                    level_remaining[i] = books[i].get(idx[i]).map_or(0.0, |l| l.1);
+                }
+            }
+
+            if take < seg {
+                break; // stopped mid-level because max_notional was reached
+            }
+        }
+
+        if cum_size <= 0.0 {
+            return None;
+        }
+
+        let vwap_per_leg: Vec<f64> = (0..n).map(|i| leg_cost[i] / leg_size[i]).collect();
+        let profit_gross = if is_mint { cum_cost - cum_size } else { cum_size - cum_cost };
+        let profit_net = profit_gross - self.config.fee * cum_size;
+        let profit_bps = (profit_net / cum_size) * 10_000.0;
+        Some((cum_size, vwap_per_leg, profit_net, profit_bps))
+    }
+
+    /// Depth-aware sizing: walk each outcome's resting book level by level
+    /// instead of assuming the headline top-of-book price fills in full, and
+    /// report the largest executable size plus the profit realized at it.
+    pub fn scan_sized(&self, condition_id: &str, books: &[Vec<(f64, f64)>]) -> Option<SizedOpportunity> {
+        if let Some((max_size, vwap_per_leg, profit_net, profit_bps)) = self.walk_depth(books, true) {
+            return Some(SizedOpportunity {
+                condition_id: condition_id.to_string(),
+                opportunity_type: "MintAndSell".to_string(),
+                max_size,
+                vwap_per_leg,
+                realized_profit_net: profit_net,
+                realized_profit_bps: profit_bps,
+            });
+        }
+        if let Some((max_size, vwap_per_leg, profit_net, profit_bps)) = self.walk_depth(books, false) {
+            return Some(SizedOpportunity {
+                condition_id: condition_id.to_string(),
+                opportunity_type: "BuyAndMerge".to_string(),
+                max_size,
+                vwap_per_leg,
+                realized_profit_net: profit_net,
+                realized_profit_bps: profit_bps,
+            });
+        }
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -247,6 +851,28 @@ mod tests {
         assert!(opp.is_profitable);
     }
 
+    #[test]
+    fn test_scan_quantizes_to_ticks_before_summing() {
+        let miner = NegRisk::new(); // default tick_size = 0.001, fee = 0.02
+
+        // 0.551 + 0.551 = 1.102 in raw f64, but repeated addition of prices
+        // that aren't exact binary fractions is exactly the drift this
+        // quantization avoids: both inputs round to the same tick (551)
+        // either way, so the threshold test is unaffected by it.
+        let opp = miner.scan("cond-tick", vec![0.551, 0.551], vec![0.6, 0.6]);
+        assert_eq!(opp.opportunity_type, "MintAndSell");
+        assert!((opp.sum_bids - 1.102).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_quick_check_agrees_with_scan_at_boundary() {
+        let miner = NegRisk::new();
+        let bids = vec![0.551, 0.551];
+        let asks = vec![0.6, 0.6];
+        let found_opportunity = miner.scan("c", bids.clone(), asks.clone()).opportunity_type != "None";
+        assert_eq!(miner.quick_check(bids, asks), found_opportunity);
+    }
+
     #[test]
     fn test_no_opportunity() {
         let miner = NegRisk::new();
@@ -256,4 +882,144 @@ mod tests {
         assert_eq!(opp.opportunity_type, "None");
         assert!(!opp.is_profitable);
     }
+
+    #[test]
+    fn test_partition_finds_mixed_opportunity() {
+        let miner = NegRisk::new();
+
+        // Three-way market: Σbids = 1.10, so minting a full set for $1 and
+        // selling A and B at their bids (C kept, marked at its bid) clears $1.
+        let opp = miner.scan_partitions("cond4", vec![0.40, 0.40, 0.30], vec![0.45, 0.45, 0.35]);
+        assert!(opp.is_some());
+        let opp = opp.unwrap();
+        assert_eq!(opp.legs.len(), 3);
+        assert!(opp.profit_gross > 0.0);
+    }
+
+    #[test]
+    fn test_partition_rejects_degenerate_market() {
+        let miner = NegRisk::new();
+
+        let opp = miner.scan_partitions("cond5", vec![0.33, 0.33, 0.33], vec![0.34, 0.34, 0.34]);
+        assert!(opp.is_none());
+    }
+
+    #[test]
+    fn test_given_partition_rejects_overlapping_groups() {
+        let miner = NegRisk::new();
+
+        let err = miner
+            .scan_given_partition("cond6", &[0.4, 0.4, 0.3], &[0.45, 0.45, 0.35], &[0, 1], &[1, 2], &[])
+            .unwrap_err();
+        assert!(matches!(err, NegRiskError::InvalidPartition(_)));
+    }
+
+    #[test]
+    fn test_given_partition_rejects_incomplete_coverage() {
+        let miner = NegRisk::new();
+
+        let err = miner
+            .scan_given_partition("cond7", &[0.4, 0.4, 0.3], &[0.45, 0.45, 0.35], &[0], &[1], &[])
+            .unwrap_err();
+        assert!(matches!(err, NegRiskError::InvalidPartition(_)));
+    }
+
+    #[test]
+    fn test_given_partition_rejects_no_trade_proposed() {
+        let miner = NegRisk::new();
+
+        let err = miner
+            .scan_given_partition("cond8", &[0.4, 0.4, 0.3], &[0.45, 0.45, 0.35], &[], &[], &[0, 1, 2])
+            .unwrap_err();
+        assert!(matches!(err, NegRiskError::InvalidPartition(_)));
+    }
+
+    #[test]
+    fn test_given_partition_values_a_mixed_buy_and_sell_grouping_as_mint_only() {
+        let miner = NegRisk::new();
+
+        // Same market as `test_partition_finds_mixed_opportunity`, but with
+        // outcome 2 explicitly marked BUY alongside SELL (0, 1). A mint
+        // already hands over outcome 2 for free, so the BUY label must not
+        // also charge its ask - profit collapses to sum(bids) - 1.0,
+        // exactly like the all-SELL/KEEP grouping would.
+        let opp = miner
+            .scan_given_partition("cond-mixed", &[0.40, 0.40, 0.30], &[0.45, 0.45, 0.35], &[2], &[0, 1], &[])
+            .unwrap()
+            .unwrap();
+
+        assert!((opp.profit_gross - 0.10).abs() < 1e-9);
+        let buy_leg = opp.legs.iter().find(|l| l.token_index == 2).unwrap();
+        assert_eq!(buy_leg.action, "MINT");
+        assert!((buy_leg.price - 0.30).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_given_partition_matches_auto_search_grouping() {
+        let miner = NegRisk::new();
+
+        // Same mixed-opportunity market as `test_partition_finds_mixed_opportunity`,
+        // but with the SELL group (0, 1) and KEEP group (2) specified directly.
+        let opp = miner
+            .scan_given_partition(
+                "cond9",
+                &[0.40, 0.40, 0.30],
+                &[0.45, 0.45, 0.35],
+                &[],
+                &[0, 1],
+                &[2],
+            )
+            .unwrap();
+        assert!(opp.is_some());
+        assert!(opp.unwrap().profit_gross > 0.0);
+    }
+
+    #[test]
+    fn test_scan_sized_stops_at_min_profit_bps() {
+        let miner = NegRisk::with_config(NegRiskConfig {
+            fee: 0.0,
+            min_profit_bps: 500.0, // require >= 5% edge
+            max_notional: 10_000.0,
+            ..NegRiskConfig::new()
+        });
+
+        // Outcome A: 2 shares @0.60 then thins out to 0.50 (edge collapses below 5%).
+        // Outcome B: flat 0.45, plenty of depth.
+        let books = vec![
+            vec![(0.60, 2.0), (0.50, 10.0)],
+            vec![(0.45, 20.0)],
+        ];
+        let opp = miner.scan_sized("cond10", &books).unwrap();
+        assert_eq!(opp.opportunity_type, "MintAndSell");
+        // Only the first (0.60, 0.45) level clears the 5% threshold: sum=1.05.
+        // The next level (0.50, 0.45) sums to 0.95, an outright loss, so the
+        // walk must stop at 2 shares rather than draining outcome B's book.
+        assert!((opp.max_size - 2.0).abs() < 1e-6);
+        assert!(opp.realized_profit_bps >= 500.0);
+    }
+
+    #[test]
+    fn test_scan_sized_caps_at_max_notional() {
+        let miner = NegRisk::with_config(NegRiskConfig {
+            fee: 0.0,
+            min_profit_bps: 0.0,
+            max_notional: 1.0, // one dollar of combined cost, total
+            ..NegRiskConfig::new()
+        });
+
+        let books = vec![vec![(0.60, 100.0)], vec![(0.45, 100.0)]];
+        let opp = miner.scan_sized("cond11", &books).unwrap();
+        // price_sum = 1.05/share, so $1 of notional buys ~0.952 shares
+        assert!(opp.max_size > 0.0 && opp.max_size < 1.0);
+    }
+
+    #[test]
+    fn test_scan_sized_none_when_no_level_clears_fees() {
+        let miner = NegRisk::new(); // default 2% fee
+
+        // Σ = 1.01 in both directions: inside [1 - fee, 1 + fee], so neither
+        // MintAndSell nor BuyAndMerge clears the fee hurdle at any size.
+        let books = vec![vec![(0.51, 5.0)], vec![(0.50, 5.0)]];
+        assert!(miner.scan_sized("cond12", &books).is_none());
+    }
 }
@@ -5,10 +5,17 @@
 //! - Bid/Ask level management
 //! - Delta application for WebSocket updates
 //! - Spread and mid-price calculations
+//! - Sequence-gap recovery: out-of-order deltas are buffered rather than
+//!   dropped, and `reconcile` fast-forwards a book past a gap from a snapshot
+//! - Versioned checkpoint/incremental-diff sync for consumers via
+//!   `checkpoint`/`updates_since`
+//! - CoinGecko-compatible depth/ticker export via `OrderbookManager`
+//! - Authoritative per-level order counts carried on `OrderbookDelta`,
+//!   rather than an ever-incrementing counter
 
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, VecDeque};
 use std::sync::RwLock;
 
 /// A single price level in the orderbook
@@ -32,74 +39,98 @@ pub struct OrderbookDelta {
     pub price: f64,
     pub size: f64,  // 0 means remove level
     pub side: String, // "bid" or "ask"
+    /// Authoritative number of resting orders at this level, for feeds that
+    /// report it. `None` for feeds that only ever send aggregate size -
+    /// those levels are treated as a single order.
+    #[serde(default)]
+    pub order_count: Option<u32>,
 }
 
-/// Half of an orderbook (either bids or asks)
+/// Default tick size (price increment) when a market doesn't specify one:
+/// 6 decimal places, matching Polymarket's USDC precision.
+pub const DEFAULT_TICK_SIZE: f64 = 0.000_001;
+
+/// Half of an orderbook (either bids or asks).
+///
+/// Prices are keyed by integer tick count rather than by a scaled `f64`, so
+/// two prices that should land on the same tick never collide or drift
+/// apart due to floating-point rounding near the conversion boundary. The
+/// map is always stored in ascending tick order; bid-side priority (highest
+/// price first) is obtained by iterating in reverse rather than inverting
+/// the key, which keeps `best_price`/`spread_bps` exact.
 #[derive(Debug)]
 pub struct OrderbookHalf {
-    /// BTreeMap for price-sorted levels
-    /// For bids: sorted descending (best bid first)
-    /// For asks: sorted ascending (best ask first)
     levels: BTreeMap<u64, PriceLevel>,
     is_bid: bool,
+    tick_size: f64,
 }
 
 impl OrderbookHalf {
     pub fn new(is_bid: bool) -> Self {
+        Self::with_tick_size(is_bid, DEFAULT_TICK_SIZE)
+    }
+
+    pub fn with_tick_size(is_bid: bool, tick_size: f64) -> Self {
         Self {
             levels: BTreeMap::new(),
             is_bid,
+            tick_size,
         }
     }
 
-    /// Convert f64 price to u64 key (6 decimal precision)
-    fn price_to_key(&self, price: f64) -> u64 {
-        if self.is_bid {
-            // Invert for bids so highest price comes first
-            u64::MAX - (price * 1_000_000.0) as u64
-        } else {
-            (price * 1_000_000.0) as u64
-        }
+    /// Convert an f64 price to its integer tick count
+    fn price_to_tick(&self, price: f64) -> u64 {
+        (price / self.tick_size).round() as u64
+    }
+
+    fn tick_to_price(&self, tick: u64) -> f64 {
+        tick as f64 * self.tick_size
     }
 
-    fn key_to_price(&self, key: u64) -> f64 {
+    /// Iterate levels in priority order: best bid (highest price) or best
+    /// ask (lowest price) first.
+    fn iter_priority(&self) -> Box<dyn Iterator<Item = (&u64, &PriceLevel)> + '_> {
         if self.is_bid {
-            (u64::MAX - key) as f64 / 1_000_000.0
+            Box::new(self.levels.iter().rev())
         } else {
-            key as f64 / 1_000_000.0
+            Box::new(self.levels.iter())
         }
     }
 
-    /// Apply a delta update
-    pub fn apply_delta(&mut self, price: f64, size: f64) {
-        let key = self.price_to_key(price);
-        
+    /// Apply a delta update. `order_count` is the authoritative resting
+    /// order count at this level if the feed reports one; `None` (the
+    /// common case for feeds that only send aggregate size) stores `1`
+    /// rather than accumulating across updates, so the field always
+    /// reflects the level's current reality instead of growing unbounded.
+    pub fn apply_delta(&mut self, price: f64, size: f64, order_count: Option<u32>) {
+        let tick = self.price_to_tick(price);
+
         if size <= 0.0 {
             // Remove level
-            self.levels.remove(&key);
+            self.levels.remove(&tick);
         } else {
-            // Update or insert level
-            self.levels.insert(key, PriceLevel {
-                price,
+            // Update or insert level, snapping the stored price to the tick grid
+            self.levels.insert(tick, PriceLevel {
+                price: self.tick_to_price(tick),
                 size,
-                order_count: 1,
+                order_count: order_count.unwrap_or(1),
             });
         }
     }
 
     /// Get the best price (top of book)
     pub fn best_price(&self) -> Option<f64> {
-        self.levels.iter().next().map(|(key, _)| self.key_to_price(*key))
+        self.iter_priority().next().map(|(tick, _)| self.tick_to_price(*tick))
     }
 
     /// Get the best level
     pub fn best_level(&self) -> Option<&PriceLevel> {
-        self.levels.values().next()
+        self.iter_priority().next().map(|(_, level)| level)
     }
 
     /// Get top N levels
     pub fn top_levels(&self, n: usize) -> Vec<PriceLevel> {
-        self.levels.values().take(n).cloned().collect()
+        self.iter_priority().take(n).map(|(_, level)| level.clone()).collect()
     }
 
     /// Get total size at all levels
@@ -107,15 +138,19 @@ impl OrderbookHalf {
         self.levels.values().map(|l| l.size).sum()
     }
 
+    /// Every level in priority order, for a full `checkpoint` snapshot.
+    pub fn all_levels(&self) -> Vec<PriceLevel> {
+        self.iter_priority().map(|(_, level)| level.clone()).collect()
+    }
+
     /// Get total size up to a price threshold
     pub fn size_to_price(&self, threshold: f64) -> f64 {
-        self.levels.iter()
-            .take_while(|(key, _)| {
-                let price = self.key_to_price(**key);
+        self.iter_priority()
+            .take_while(|(_, level)| {
                 if self.is_bid {
-                    price >= threshold
+                    level.price >= threshold
                 } else {
-                    price <= threshold
+                    level.price <= threshold
                 }
             })
             .map(|(_, level)| level.size)
@@ -131,6 +166,160 @@ impl OrderbookHalf {
     pub fn depth(&self) -> usize {
         self.levels.len()
     }
+
+    /// Walk levels in priority order, filling up to `size`.
+    /// Returns the per-level fills consumed and the unfilled remainder.
+    pub fn fill(&self, size: f64) -> (Vec<(f64, f64)>, f64) {
+        let mut remaining = size;
+        let mut fills = Vec::new();
+
+        for (_, level) in self.iter_priority() {
+            if remaining <= 0.0 {
+                break;
+            }
+            let take = level.size.min(remaining);
+            fills.push((level.price, take));
+            remaining -= take;
+        }
+
+        (fills, remaining.max(0.0))
+    }
+
+    /// Size-weighted average fill price (VWAP) to execute `size`.
+    /// Returns `None` if the book cannot fill any of the requested size.
+    pub fn price_for_size(&self, size: f64) -> Option<f64> {
+        let (fills, _remaining) = self.fill(size);
+        if fills.is_empty() {
+            return None;
+        }
+        let filled: f64 = fills.iter().map(|(_, s)| s).sum();
+        if filled <= 0.0 {
+            return None;
+        }
+        let notional: f64 = fills.iter().map(|(p, s)| p * s).sum();
+        Some(notional / filled)
+    }
+}
+
+/// Result of an IOC/marketable-take simulation against one side of the book
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[pyclass]
+pub struct Fill {
+    #[pyo3(get)]
+    pub avg_price: f64,
+    #[pyo3(get)]
+    pub filled: f64,
+    #[pyo3(get)]
+    pub remaining: f64,
+    #[pyo3(get)]
+    pub levels_consumed: usize,
+}
+
+#[pymethods]
+impl Fill {
+    fn __repr__(&self) -> String {
+        format!(
+            "Fill(avg_price={:.4}, filled={}, remaining={}, levels={})",
+            self.avg_price, self.filled, self.remaining, self.levels_consumed
+        )
+    }
+}
+
+/// Configuration for `StablePrice`'s delay-limited tracking
+#[derive(Debug, Clone, Copy)]
+pub struct StablePriceConfig {
+    /// Maximum fractional change allowed per second (e.g. 0.01 = 1%/sec)
+    pub max_change_per_sec: f64,
+}
+
+impl Default for StablePriceConfig {
+    fn default() -> Self {
+        Self { max_change_per_sec: 0.01 }
+    }
+}
+
+/// A delay-limited mid price (à la Mango's stable-price model) that lags
+/// sudden single-quote spikes rather than jumping with the raw
+/// best-bid/best-ask midpoint. Each update moves all the way to the observed
+/// mid when that move is within the per-second clamp; no exponential
+/// weighting is applied.
+#[derive(Debug, Clone)]
+pub struct StablePrice {
+    config: StablePriceConfig,
+    value: Option<f64>,
+    last_update_ts: u64,
+}
+
+impl StablePrice {
+    pub fn new(config: StablePriceConfig) -> Self {
+        Self { config, value: None, last_update_ts: 0 }
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        self.value
+    }
+
+    /// Move toward `observed_mid`, clamped so the stable price can move by
+    /// at most `max_change_per_sec * elapsed_secs` as a fraction of itself.
+    pub fn update(&mut self, observed_mid: f64, ts: u64) {
+        let current = match self.value {
+            None => {
+                self.value = Some(observed_mid);
+                self.last_update_ts = ts;
+                return;
+            }
+            Some(v) => v,
+        };
+
+        let elapsed = ts.saturating_sub(self.last_update_ts).max(1) as f64;
+        let max_move = current.abs() * self.config.max_change_per_sec * elapsed;
+        let delta = (observed_mid - current).clamp(-max_move, max_move);
+
+        self.value = Some(current + delta);
+        self.last_update_ts = ts;
+    }
+}
+
+/// A full top-of-book snapshot used to resync a book whose sequence has
+/// gapped further than the buffered deltas can repair on their own.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderbookSnapshot {
+    pub sequence: u64,
+    pub bids: Vec<PriceLevel>,
+    pub asks: Vec<PriceLevel>,
+}
+
+/// One price level changing, as recorded by the incremental diff stream -
+/// `size == 0.0` means the level was removed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LevelUpdate {
+    pub side: String, // "bid" or "ask"
+    pub price: f64,
+    pub size: f64,
+}
+
+/// A full snapshot of the book at `version`, for a consumer to sync against
+/// before switching to compact `updates_since` diffs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BookCheckpoint {
+    pub version: u64,
+    pub bids: Vec<PriceLevel>,
+    pub asks: Vec<PriceLevel>,
+}
+
+/// Number of recent level-updates retained for `updates_since` before the
+/// window rolls over and a consumer must resync with a fresh `checkpoint`.
+const UPDATE_WINDOW: usize = 512;
+
+/// Outcome of feeding a sequenced delta into `Orderbook::apply_sequenced_delta`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApplyOutcome {
+    /// The delta was contiguous with the book's sequence (or stale) and was
+    /// applied, or discarded, in place.
+    Applied,
+    /// The delta arrived ahead of the book's sequence; it has been buffered
+    /// and the caller should fetch a snapshot and call `reconcile`.
+    RecoveryNeeded,
 }
 
 /// Full orderbook for a single market/token
@@ -140,29 +329,198 @@ pub struct Orderbook {
     pub bids: RwLock<OrderbookHalf>,
     pub asks: RwLock<OrderbookHalf>,
     pub last_update_ts: RwLock<u64>,
+    stable_price: RwLock<StablePrice>,
+    /// Last sequence number applied to this book. Only meaningful for feeds
+    /// that drive the book through `apply_sequenced_delta`/`reconcile`;
+    /// plain `apply_delta` callers (backtests, tests) never touch it.
+    sequence_number: RwLock<u64>,
+    /// Deltas that arrived ahead of `sequence_number`, keyed by their own
+    /// sequence number, waiting for either the missing deltas to arrive or
+    /// a snapshot `reconcile` to fast-forward past them.
+    pending: RwLock<BTreeMap<u64, OrderbookDelta>>,
+    /// Monotonically increasing version, bumped once per applied delta -
+    /// the value `checkpoint`/`updates_since` sync against.
+    version: RwLock<u64>,
+    /// The last `UPDATE_WINDOW` level-updates, each tagged with the version
+    /// it was applied at, for `updates_since` to replay compactly.
+    recent_updates: RwLock<VecDeque<(u64, LevelUpdate)>>,
 }
 
 impl Orderbook {
     pub fn new(token_id: String) -> Self {
+        Self::with_tick_size(token_id, DEFAULT_TICK_SIZE)
+    }
+
+    /// Create a book quantized to a market-specific tick size (price increment).
+    pub fn with_tick_size(token_id: String, tick_size: f64) -> Self {
         Self {
             token_id,
-            bids: RwLock::new(OrderbookHalf::new(true)),
-            asks: RwLock::new(OrderbookHalf::new(false)),
+            bids: RwLock::new(OrderbookHalf::with_tick_size(true, tick_size)),
+            asks: RwLock::new(OrderbookHalf::with_tick_size(false, tick_size)),
             last_update_ts: RwLock::new(0),
+            stable_price: RwLock::new(StablePrice::new(StablePriceConfig::default())),
+            sequence_number: RwLock::new(0),
+            pending: RwLock::new(BTreeMap::new()),
+            version: RwLock::new(0),
+            recent_updates: RwLock::new(VecDeque::new()),
         }
     }
 
     /// Apply a delta update
     pub fn apply_delta(&self, delta: &OrderbookDelta) {
-        match delta.side.to_lowercase().as_str() {
-            "bid" | "buy" => {
-                self.bids.write().unwrap().apply_delta(delta.price, delta.size);
+        let is_bid = match delta.side.to_lowercase().as_str() {
+            "bid" | "buy" => true,
+            "ask" | "sell" => false,
+            _ => return,
+        };
+
+        if is_bid {
+            self.bids.write().unwrap().apply_delta(delta.price, delta.size, delta.order_count);
+        } else {
+            self.asks.write().unwrap().apply_delta(delta.price, delta.size, delta.order_count);
+        }
+
+        self.record_version(LevelUpdate {
+            side: if is_bid { "bid" } else { "ask" }.to_string(),
+            price: delta.price,
+            size: delta.size,
+        });
+    }
+
+    /// Bump `version` and push `update` onto the retained window, dropping
+    /// the oldest entry once the window exceeds `UPDATE_WINDOW`.
+    fn record_version(&self, update: LevelUpdate) {
+        let mut version = self.version.write().unwrap();
+        *version += 1;
+
+        let mut recent = self.recent_updates.write().unwrap();
+        recent.push_back((*version, update));
+        while recent.len() > UPDATE_WINDOW {
+            recent.pop_front();
+        }
+    }
+
+    /// Apply a delta from a sequenced feed (e.g. a WebSocket diff stream).
+    /// A gap-free continuation (`sequence == sequence_number + 1`) is
+    /// applied immediately and then drains any buffered deltas that are now
+    /// contiguous. A delta at or behind the current sequence is a
+    /// stale/duplicate resend and is dropped. Anything further ahead is
+    /// buffered in `pending` rather than applied out of order, and the
+    /// caller is told a resync is needed - see `reconcile`.
+    pub fn apply_sequenced_delta(&self, sequence: u64, delta: OrderbookDelta) -> ApplyOutcome {
+        let mut seq = self.sequence_number.write().unwrap();
+        if sequence <= *seq {
+            return ApplyOutcome::Applied;
+        }
+        if sequence == *seq + 1 {
+            self.apply_delta(&delta);
+            *seq = sequence;
+            self.drain_pending_locked(&mut seq);
+            ApplyOutcome::Applied
+        } else {
+            self.pending.write().unwrap().insert(sequence, delta);
+            ApplyOutcome::RecoveryNeeded
+        }
+    }
+
+    /// Drain `pending` deltas in ascending sequence order as long as each
+    /// one is the immediate successor of `seq`, stopping at the first
+    /// remaining gap (which stays buffered for the next drain/reconcile).
+    fn drain_pending_locked(&self, seq: &mut u64) {
+        let mut pending = self.pending.write().unwrap();
+        while let Some(delta) = pending.remove(&(*seq + 1)) {
+            self.apply_delta(&delta);
+            *seq += 1;
+        }
+    }
+
+    /// Rebuild both sides of the book from `snapshot`, adopt its sequence
+    /// number, then drain any buffered deltas that are now contiguous.
+    /// Invariant: after this call the applied sequence is always contiguous
+    /// from `snapshot.sequence` forward - any remaining gap stays in
+    /// `pending` for a later delta or reconcile to resolve. Every buffered
+    /// entry at or behind the adopted sequence is discarded too, since a
+    /// delta `drain_pending_locked` will never reach (it only pops exact
+    /// successors of `seq`) would otherwise linger in `pending` forever and
+    /// keep `in_recovery()` reporting `true` after the book has caught up.
+    pub fn reconcile(&self, snapshot: OrderbookSnapshot) {
+        {
+            let mut bids = self.bids.write().unwrap();
+            bids.clear();
+            for level in &snapshot.bids {
+                bids.apply_delta(level.price, level.size, Some(level.order_count));
             }
-            "ask" | "sell" => {
-                self.asks.write().unwrap().apply_delta(delta.price, delta.size);
+        }
+        {
+            let mut asks = self.asks.write().unwrap();
+            asks.clear();
+            for level in &snapshot.asks {
+                asks.apply_delta(level.price, level.size, Some(level.order_count));
             }
-            _ => {}
         }
+
+        for level in &snapshot.bids {
+            self.record_version(LevelUpdate { side: "bid".to_string(), price: level.price, size: level.size });
+        }
+        for level in &snapshot.asks {
+            self.record_version(LevelUpdate { side: "ask".to_string(), price: level.price, size: level.size });
+        }
+
+        let mut seq = self.sequence_number.write().unwrap();
+        *seq = snapshot.sequence;
+        self.drain_pending_locked(&mut seq);
+        self.pending.write().unwrap().retain(|&k, _| k > *seq);
+    }
+
+    /// Last sequence number applied via `apply_sequenced_delta`/`reconcile`.
+    pub fn sequence_number(&self) -> u64 {
+        *self.sequence_number.read().unwrap()
+    }
+
+    /// Whether this book has deltas buffered behind a sequence gap, i.e. is
+    /// waiting on a snapshot `reconcile` to catch up.
+    pub fn in_recovery(&self) -> bool {
+        !self.pending.read().unwrap().is_empty()
+    }
+
+    /// Current version, bumped once per applied delta.
+    pub fn version(&self) -> u64 {
+        *self.version.read().unwrap()
+    }
+
+    /// A full snapshot of both sides plus the version a consumer can later
+    /// pass to `updates_since` to resume with compact incremental diffs.
+    pub fn checkpoint(&self) -> BookCheckpoint {
+        BookCheckpoint {
+            version: self.version(),
+            bids: self.bids.read().unwrap().all_levels(),
+            asks: self.asks.read().unwrap().all_levels(),
+        }
+    }
+
+    /// Level-updates applied since `version`, for a consumer already
+    /// holding a `checkpoint` at that version. Returns `None` - meaning
+    /// "fall back to a full checkpoint" - if `version` has aged out of the
+    /// retained window.
+    pub fn updates_since(&self, version: u64) -> Option<Vec<LevelUpdate>> {
+        if version >= self.version() {
+            return Some(Vec::new());
+        }
+
+        let recent = self.recent_updates.read().unwrap();
+        if let Some((oldest, _)) = recent.front() {
+            if *oldest > version + 1 {
+                return None;
+            }
+        }
+
+        Some(
+            recent
+                .iter()
+                .filter(|(v, _)| *v > version)
+                .map(|(_, update)| update.clone())
+                .collect(),
+        )
     }
 
     /// Get the current spread
@@ -195,26 +553,102 @@ impl Orderbook {
     pub fn is_wide_spread(&self, threshold_bps: f64) -> bool {
         self.spread_bps().map(|s| s >= threshold_bps).unwrap_or(false)
     }
+
+    /// Current raw best-bid/best-ask midpoint (same as `mid_price`, named to
+    /// contrast with the manipulation-resistant `stable_mid`)
+    pub fn oracle_mid(&self) -> Option<f64> {
+        self.mid_price()
+    }
+
+    /// Manipulation-resistant stable mid, delay-limited against sudden spikes
+    pub fn stable_mid(&self) -> Option<f64> {
+        self.stable_price.read().unwrap().value()
+    }
+
+    /// Advance the stable-price tracker toward the current oracle mid.
+    /// Call this whenever the book is updated and a timestamp is available.
+    pub fn update_stable_price(&self, ts: u64) {
+        if let Some(mid) = self.oracle_mid() {
+            self.stable_price.write().unwrap().update(mid, ts);
+        }
+    }
+
+    /// Simulate an IOC/marketable-take: consume levels on the opposing side
+    /// of `side` until `size` is filled or `limit_price` is exhausted.
+    /// A buy (`Side::Bid`) takes from the asks; a sell (`Side::Ask`) takes from the bids.
+    pub fn simulate_take(&self, side: Side, size: f64, limit_price: Option<f64>) -> Fill {
+        let half = match side {
+            Side::Bid => self.asks.read().unwrap(),
+            Side::Ask => self.bids.read().unwrap(),
+        };
+
+        let mut remaining = size;
+        let mut filled = 0.0;
+        let mut notional = 0.0;
+        let mut levels_consumed = 0;
+
+        for (_, level) in half.iter_priority() {
+            if remaining <= 0.0 {
+                break;
+            }
+            if let Some(limit) = limit_price {
+                let crosses = match side {
+                    Side::Bid => level.price <= limit,
+                    Side::Ask => level.price >= limit,
+                };
+                if !crosses {
+                    break;
+                }
+            }
+            let take = level.size.min(remaining);
+            filled += take;
+            notional += level.price * take;
+            remaining -= take;
+            levels_consumed += 1;
+        }
+
+        Fill {
+            avg_price: if filled > 0.0 { notional / filled } else { 0.0 },
+            filled,
+            remaining: remaining.max(0.0),
+            levels_consumed,
+        }
+    }
 }
 
 /// Manager for multiple orderbooks
 #[derive(Debug, Default)]
 pub struct OrderbookManager {
     books: RwLock<std::collections::HashMap<String, std::sync::Arc<Orderbook>>>,
+    candles: std::sync::Arc<crate::candles::CandleStore>,
 }
 
 impl OrderbookManager {
     pub fn new() -> Self {
         Self {
             books: RwLock::new(std::collections::HashMap::new()),
+            candles: std::sync::Arc::new(crate::candles::CandleStore::new()),
         }
     }
 
-    /// Get or create an orderbook for a token
+    /// The OHLCV/quote-history aggregator fed by `apply_delta`
+    pub fn candles(&self) -> &std::sync::Arc<crate::candles::CandleStore> {
+        &self.candles
+    }
+
+    /// Get or create an orderbook for a token, using the default tick size.
+    /// Existing callers keep working unchanged; use `get_or_create_with_tick`
+    /// for markets that need a specific tick size.
     pub fn get_or_create(&self, token_id: &str) -> std::sync::Arc<Orderbook> {
+        self.get_or_create_with_tick(token_id, DEFAULT_TICK_SIZE)
+    }
+
+    /// Get or create an orderbook for a token with an explicit tick size.
+    /// Has no effect if the book already exists.
+    pub fn get_or_create_with_tick(&self, token_id: &str, tick_size: f64) -> std::sync::Arc<Orderbook> {
         let mut books = self.books.write().unwrap();
         books.entry(token_id.to_string())
-            .or_insert_with(|| std::sync::Arc::new(Orderbook::new(token_id.to_string())))
+            .or_insert_with(|| std::sync::Arc::new(Orderbook::with_tick_size(token_id.to_string(), tick_size)))
             .clone()
     }
 
@@ -223,10 +657,71 @@ impl OrderbookManager {
         self.books.read().unwrap().get(token_id).cloned()
     }
 
+    /// Shared post-apply bookkeeping for every path that can change a book's
+    /// best bid/ask: refresh `stable_price` and feed the candle/quote
+    /// history. `apply_delta`, `apply_sequenced_delta`, and `reconcile` all
+    /// route through this so `stable_mid` and candles stay live no matter
+    /// which transport (toy delta, sequenced WS feed, or resync snapshot)
+    /// is driving the book.
+    fn record_tick(&self, token_id: &str, book: &Orderbook, volume: f64) {
+        let ts = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        book.update_stable_price(ts);
+        if let Some(mid) = book.mid_price() {
+            self.candles.record_mid(token_id, ts, mid, volume);
+        }
+        self.candles.record_quote(
+            token_id,
+            ts,
+            book.bids.read().unwrap().best_price(),
+            book.asks.read().unwrap().best_price(),
+        );
+    }
+
     /// Apply a delta to a specific orderbook
     pub fn apply_delta(&self, token_id: &str, delta: &OrderbookDelta) {
         let book = self.get_or_create(token_id);
         book.apply_delta(delta);
+        self.record_tick(token_id, &book, delta.size);
+    }
+
+    /// Apply a sequenced delta to a specific orderbook, buffering it instead
+    /// of applying out of order if it arrives ahead of the book's sequence.
+    pub fn apply_sequenced_delta(
+        &self,
+        token_id: &str,
+        sequence: u64,
+        delta: OrderbookDelta,
+    ) -> ApplyOutcome {
+        let book = self.get_or_create(token_id);
+        let volume = delta.size;
+        let outcome = book.apply_sequenced_delta(sequence, delta);
+        if outcome == ApplyOutcome::Applied {
+            self.record_tick(token_id, &book, volume);
+        }
+        outcome
+    }
+
+    /// Rebuild `token_id`'s book from a fresh snapshot, resolving whatever
+    /// sequence gap put it into recovery.
+    pub fn reconcile(&self, token_id: &str, snapshot: OrderbookSnapshot) {
+        let book = self.get_or_create(token_id);
+        book.reconcile(snapshot);
+        // A resync snapshot isn't a single trade, so there's no meaningful
+        // per-tick volume to attribute to this candle bucket.
+        self.record_tick(token_id, &book, 0.0);
+    }
+
+    /// Tokens whose book currently has deltas buffered behind a sequence
+    /// gap, i.e. need a snapshot `reconcile` before they fully catch up.
+    pub fn request_resync(&self) -> Vec<String> {
+        self.books.read().unwrap()
+            .iter()
+            .filter(|(_, book)| book.in_recovery())
+            .map(|(id, _)| id.clone())
+            .collect()
     }
 
     /// Find markets with wide spreads
@@ -242,13 +737,83 @@ impl OrderbookManager {
     pub fn token_ids(&self) -> Vec<String> {
         self.books.read().unwrap().keys().cloned().collect()
     }
+
+    /// CoinGecko-style depth export for `token_id`: the best `depth` levels
+    /// on each side, best-priced first, alongside the export timestamp.
+    pub fn depth_export(&self, token_id: &str, depth: usize) -> Option<DepthExport> {
+        let book = self.get(token_id)?;
+        let bids = book.bids.read().unwrap().top_levels(depth);
+        let asks = book.asks.read().unwrap().top_levels(depth);
+
+        Some(DepthExport {
+            timestamp: now_secs(),
+            bids: bids.into_iter().map(|l| (l.price, l.size)).collect(),
+            asks: asks.into_iter().map(|l| (l.price, l.size)).collect(),
+        })
+    }
+
+    /// CoinGecko-style ticker export for `token_id`: current best bid/ask
+    /// and last (mid) price, plus high/low over the trailing 24h of candles.
+    pub fn ticker_export(&self, token_id: &str) -> Option<Ticker> {
+        let book = self.get(token_id)?;
+        let (high, low, _volume) = self.candles.high_low_volume(token_id, now_secs(), 86_400);
+
+        Some(Ticker {
+            ticker_id: token_id.to_string(),
+            base: token_id.to_string(),
+            target: "USDC".to_string(),
+            bid: book.bids.read().unwrap().best_price(),
+            ask: book.asks.read().unwrap().best_price(),
+            last: book.mid_price(),
+            high: finite_or_none(high),
+            low: finite_or_none(low),
+        })
+    }
+}
+
+/// CoinGecko-compatible depth export: best-priced levels first on each side.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepthExport {
+    pub timestamp: u64,
+    pub bids: Vec<(f64, f64)>,
+    pub asks: Vec<(f64, f64)>,
+}
+
+/// CoinGecko-compatible ticker export, matching the common aggregator schema.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ticker {
+    pub ticker_id: String,
+    pub base: String,
+    pub target: String,
+    pub bid: Option<f64>,
+    pub ask: Option<f64>,
+    pub last: Option<f64>,
+    pub high: Option<f64>,
+    pub low: Option<f64>,
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// `high_low_volume_since` folds over an empty range with `f64::MIN`/`MAX`
+/// sentinels rather than a real value - map those back to `None`.
+fn finite_or_none(value: f64) -> Option<f64> {
+    if value.is_finite() && value != f64::MIN && value != f64::MAX {
+        Some(value)
+    } else {
+        None
+    }
 }
 
 // ============ PyO3 Bindings ============
 
 #[pyclass]
 pub struct PyOrderbook {
-    inner: std::sync::Arc<Orderbook>,
+    pub(crate) inner: std::sync::Arc<Orderbook>,
 }
 
 #[pymethods]
@@ -260,12 +825,16 @@ impl PyOrderbook {
         }
     }
 
-    /// Apply a delta update: (price, size, side)
-    fn apply_delta(&self, price: f64, size: f64, side: &str) {
+    /// Apply a delta update: (price, size, side). `order_count`, if the
+    /// feed reports one, is stored as the level's authoritative resting
+    /// order count; omitted, the level is treated as a single order.
+    #[pyo3(signature = (price, size, side, order_count=None))]
+    fn apply_delta(&self, price: f64, size: f64, side: &str, order_count: Option<u32>) {
         self.inner.apply_delta(&OrderbookDelta {
             price,
             size,
             side: side.to_string(),
+            order_count,
         });
     }
 
@@ -284,6 +853,16 @@ impl PyOrderbook {
         self.inner.mid_price()
     }
 
+    /// Current raw best-bid/best-ask midpoint
+    fn oracle_mid(&self) -> Option<f64> {
+        self.inner.oracle_mid()
+    }
+
+    /// Manipulation-resistant stable mid, delay-limited against sudden spikes
+    fn stable_mid(&self) -> Option<f64> {
+        self.inner.stable_mid()
+    }
+
     /// Get spread in basis points
     fn spread_bps(&self) -> Option<f64> {
         self.inner.spread_bps()
@@ -316,6 +895,86 @@ impl PyOrderbook {
         self.inner.asks.read().unwrap().depth()
     }
 
+    /// VWAP to buy `size` by walking the asks
+    fn price_for_size_buy(&self, size: f64) -> Option<f64> {
+        self.inner.asks.read().unwrap().price_for_size(size)
+    }
+
+    /// VWAP to sell `size` by walking the bids
+    fn price_for_size_sell(&self, size: f64) -> Option<f64> {
+        self.inner.bids.read().unwrap().price_for_size(size)
+    }
+
+    /// Simulate a marketable buy of `size`, optionally capped at `limit_price`
+    fn simulate_buy(&self, size: f64, limit_price: Option<f64>) -> Fill {
+        self.inner.simulate_take(Side::Bid, size, limit_price)
+    }
+
+    /// Simulate a marketable sell of `size`, optionally capped at `limit_price`
+    fn simulate_sell(&self, size: f64, limit_price: Option<f64>) -> Fill {
+        self.inner.simulate_take(Side::Ask, size, limit_price)
+    }
+
+    /// Apply a delta carrying the feed's sequence number instead of raising
+    /// it out of band. Returns `true` if it applied cleanly, `false` if it
+    /// was buffered behind a gap and this book now needs `reconcile`.
+    #[pyo3(signature = (sequence, price, size, side, order_count=None))]
+    fn apply_sequenced_delta(
+        &self,
+        sequence: u64,
+        price: f64,
+        size: f64,
+        side: &str,
+        order_count: Option<u32>,
+    ) -> bool {
+        let outcome = self.inner.apply_sequenced_delta(
+            sequence,
+            OrderbookDelta { price, size, side: side.to_string(), order_count },
+        );
+        outcome == ApplyOutcome::Applied
+    }
+
+    /// Rebuild this book from a `{"sequence", "bids", "asks"}` snapshot JSON
+    /// (each side a list of `{"price", "size", "order_count"}`), resolving
+    /// whatever sequence gap put it into recovery.
+    fn reconcile(&self, snapshot_json: &str) -> PyResult<()> {
+        let snapshot: OrderbookSnapshot = serde_json::from_str(snapshot_json)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        self.inner.reconcile(snapshot);
+        Ok(())
+    }
+
+    /// Last sequence number applied via `apply_sequenced_delta`/`reconcile`
+    fn sequence_number(&self) -> u64 {
+        self.inner.sequence_number()
+    }
+
+    /// Whether this book is waiting on a snapshot `reconcile` to resolve a
+    /// sequence gap
+    fn in_recovery(&self) -> bool {
+        self.inner.in_recovery()
+    }
+
+    /// Current version, bumped once per applied delta
+    fn version(&self) -> u64 {
+        self.inner.version()
+    }
+
+    /// Full `{version, bids, asks}` snapshot as JSON. A consumer syncs once
+    /// from this, then calls `updates_since` to stay current.
+    fn checkpoint_json(&self) -> String {
+        serde_json::to_string(&self.inner.checkpoint()).unwrap_or_default()
+    }
+
+    /// Compact `{side, price, size}` level-updates applied since `version`,
+    /// as JSON, or `None` if that version has aged out of the retained
+    /// window - reconnect with a fresh `checkpoint_json` in that case.
+    fn updates_since_json(&self, version: u64) -> Option<String> {
+        self.inner
+            .updates_since(version)
+            .map(|updates| serde_json::to_string(&updates).unwrap_or_default())
+    }
+
     fn __repr__(&self) -> String {
         let bid = self.best_bid().map(|p| format!("{:.4}", p)).unwrap_or("--".into());
         let ask = self.best_ask().map(|p| format!("{:.4}", p)).unwrap_or("--".into());
@@ -338,12 +997,16 @@ impl PyOrderbookManager {
         }
     }
 
-    /// Apply a delta to a token's orderbook
-    fn apply_delta(&self, token_id: &str, price: f64, size: f64, side: &str) {
+    /// Apply a delta to a token's orderbook. `order_count`, if the feed
+    /// reports one, is stored as the level's authoritative resting order
+    /// count; omitted, the level is treated as a single order.
+    #[pyo3(signature = (token_id, price, size, side, order_count=None))]
+    fn apply_delta(&self, token_id: &str, price: f64, size: f64, side: &str, order_count: Option<u32>) {
         self.inner.apply_delta(token_id, &OrderbookDelta {
             price,
             size,
             side: side.to_string(),
+            order_count,
         });
     }
 
@@ -362,12 +1025,207 @@ impl PyOrderbookManager {
         self.inner.find_wide_spreads(threshold_bps)
     }
 
+    /// Get candles for a token/resolution ("1s", "1m", "5m", "1h") as JSON
+    fn get_candles_json(&self, token_id: &str, resolution: &str, from_ts: u64, to_ts: u64) -> String {
+        let Some(res) = crate::candles::Resolution::from_str_pub(resolution) else {
+            return "[]".to_string();
+        };
+        serde_json::to_string(&self.inner.candles().get_candles(token_id, res, from_ts, to_ts)).unwrap_or_default()
+    }
+
+    /// 24h high/low/volume for a token, anchored at `now_ts`
+    fn high_low_volume_24h(&self, token_id: &str, now_ts: u64) -> (f64, f64, f64) {
+        self.inner.candles().high_low_volume(token_id, now_ts, 86_400)
+    }
+
     /// Get all token IDs
     fn token_ids(&self) -> Vec<String> {
         self.inner.token_ids()
     }
 
+    /// Tokens currently waiting on a snapshot `reconcile` to resolve a
+    /// sequence gap
+    fn request_resync(&self) -> Vec<String> {
+        self.inner.request_resync()
+    }
+
+    /// CoinGecko-style `{timestamp, bids, asks}` depth export for a token,
+    /// as JSON, `None` if the token has no book.
+    fn orderbook_depth_json(&self, token_id: &str, depth: usize) -> Option<String> {
+        serde_json::to_string(&self.inner.depth_export(token_id, depth)?).ok()
+    }
+
+    /// CoinGecko-style ticker export for a token, as JSON, `None` if the
+    /// token has no book.
+    fn ticker_json(&self, token_id: &str) -> Option<String> {
+        serde_json::to_string(&self.inner.ticker_export(token_id)?).ok()
+    }
+
     fn __repr__(&self) -> String {
         format!("OrderbookManager(tokens={})", self.inner.token_ids().len())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_delta_without_order_count_treats_level_as_one_order() {
+        let mut half = OrderbookHalf::new(true);
+        half.apply_delta(10.0, 5.0, None);
+        half.apply_delta(10.0, 7.0, None);
+        half.apply_delta(10.0, 9.0, None);
+
+        assert_eq!(half.best_level().unwrap().order_count, 1);
+    }
+
+    #[test]
+    fn apply_delta_stores_authoritative_order_count() {
+        let mut half = OrderbookHalf::new(true);
+        half.apply_delta(10.0, 5.0, Some(3));
+        assert_eq!(half.best_level().unwrap().order_count, 3);
+
+        half.apply_delta(10.0, 2.0, Some(1));
+        assert_eq!(half.best_level().unwrap().order_count, 1);
+    }
+
+    #[test]
+    fn apply_delta_removes_level_and_its_order_count_on_zero_size() {
+        let mut half = OrderbookHalf::new(true);
+        half.apply_delta(10.0, 5.0, Some(4));
+        assert_eq!(half.depth(), 1);
+
+        half.apply_delta(10.0, 0.0, None);
+        assert_eq!(half.depth(), 0);
+        assert!(half.best_level().is_none());
+    }
+
+    #[test]
+    fn price_for_size_is_size_weighted_across_levels() {
+        let mut bids = OrderbookHalf::new(true);
+        bids.apply_delta(10.0, 5.0, None);
+        bids.apply_delta(9.0, 5.0, None);
+
+        // 8 units: all 5 at 10.0, then 3 at 9.0 -> (5*10 + 3*9) / 8
+        assert!((bids.price_for_size(8.0).unwrap() - 9.625).abs() < 1e-9);
+    }
+
+    #[test]
+    fn price_for_size_returns_none_when_book_is_empty() {
+        let bids = OrderbookHalf::new(true);
+        assert!(bids.price_for_size(1.0).is_none());
+    }
+
+    #[test]
+    fn price_for_size_vwaps_only_what_the_book_can_fill() {
+        let mut asks = OrderbookHalf::new(false);
+        asks.apply_delta(10.0, 4.0, None);
+
+        // Book only has 4 units; requesting 100 still VWAPs the 4 it has.
+        assert_eq!(asks.price_for_size(100.0).unwrap(), 10.0);
+    }
+
+    #[test]
+    fn tick_size_quantizes_distinct_prices_without_collision() {
+        let mut half = OrderbookHalf::with_tick_size(true, 0.01);
+        half.apply_delta(1.004, 5.0, None);
+        half.apply_delta(0.996, 5.0, None);
+
+        // Both snap to the same 0.01 tick and should merge into one level,
+        // not silently overwrite each other with mismatched raw f64 prices.
+        assert_eq!(half.depth(), 1);
+        assert_eq!(half.best_level().unwrap().price, 1.0);
+    }
+
+    #[test]
+    fn tick_size_keeps_adjacent_ticks_distinct() {
+        let mut half = OrderbookHalf::with_tick_size(true, 0.01);
+        half.apply_delta(1.00, 5.0, None);
+        half.apply_delta(1.01, 5.0, None);
+
+        assert_eq!(half.depth(), 2);
+        assert_eq!(half.best_level().unwrap().price, 1.01);
+    }
+
+    #[test]
+    fn stable_price_seeds_from_first_observation() {
+        let mut sp = StablePrice::new(StablePriceConfig::default());
+        sp.update(100.0, 1_000);
+        assert_eq!(sp.value(), Some(100.0));
+    }
+
+    #[test]
+    fn stable_price_clamps_large_moves_but_converges_over_time() {
+        let config = StablePriceConfig { max_change_per_sec: 0.01 };
+        let mut sp = StablePrice::new(config);
+        sp.update(100.0, 0);
+        sp.update(200.0, 1);
+
+        // At most 1%/sec of 100.0 in the first second: can't jump straight to 200.
+        let after_one_sec = sp.value().unwrap();
+        assert!(after_one_sec < 101.5, "moved too far in one second: {after_one_sec}");
+
+        // Feeding the same observed mid for long enough should fully converge.
+        sp.update(200.0, 10_000);
+        assert!((sp.value().unwrap() - 200.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn reconcile_clears_stale_pending_entries_and_ends_recovery() {
+        let book = Orderbook::new("tok".to_string());
+        book.apply_sequenced_delta(5, OrderbookDelta { price: 1.0, size: 1.0, side: "bid".to_string(), order_count: None });
+        book.apply_sequenced_delta(7, OrderbookDelta { price: 1.0, size: 1.0, side: "bid".to_string(), order_count: None });
+        assert!(book.in_recovery());
+
+        book.reconcile(OrderbookSnapshot { sequence: 8, bids: vec![], asks: vec![] });
+
+        assert!(!book.in_recovery());
+        assert_eq!(book.sequence_number(), 8);
+    }
+
+    #[test]
+    fn reconcile_is_recorded_in_the_update_stream() {
+        let book = Orderbook::new("tok".to_string());
+        let before = book.version();
+
+        book.reconcile(OrderbookSnapshot {
+            sequence: 1,
+            bids: vec![PriceLevel { price: 1.0, size: 2.0, order_count: 1 }],
+            asks: vec![],
+        });
+
+        assert!(book.version() > before);
+        let updates = book.updates_since(before).unwrap();
+        assert!(updates.iter().any(|u| u.side == "bid" && u.price == 1.0));
+    }
+
+    #[test]
+    fn depth_export_returns_best_priced_levels_first() {
+        let manager = OrderbookManager::new();
+        manager.apply_delta("tok", &OrderbookDelta { price: 10.0, size: 1.0, side: "bid".to_string(), order_count: None });
+        manager.apply_delta("tok", &OrderbookDelta { price: 9.0, size: 1.0, side: "bid".to_string(), order_count: None });
+        manager.apply_delta("tok", &OrderbookDelta { price: 11.0, size: 1.0, side: "ask".to_string(), order_count: None });
+
+        let depth = manager.depth_export("tok", 5).unwrap();
+        assert_eq!(depth.bids[0].0, 10.0);
+        assert_eq!(depth.asks[0].0, 11.0);
+    }
+
+    #[test]
+    fn ticker_export_reports_current_best_bid_and_ask() {
+        let manager = OrderbookManager::new();
+        manager.apply_delta("tok", &OrderbookDelta { price: 10.0, size: 1.0, side: "bid".to_string(), order_count: None });
+        manager.apply_delta("tok", &OrderbookDelta { price: 11.0, size: 1.0, side: "ask".to_string(), order_count: None });
+
+        let ticker = manager.ticker_export("tok").unwrap();
+        assert_eq!(ticker.bid, Some(10.0));
+        assert_eq!(ticker.ask, Some(11.0));
+    }
+
+    #[test]
+    fn ticker_export_returns_none_for_unknown_token() {
+        let manager = OrderbookManager::new();
+        assert!(manager.ticker_export("missing").is_none());
+    }
+}
@@ -0,0 +1,334 @@
+//! Portfolio Module - Infrastructure: Cross-Bot Capital & Margin Engine
+//!
+//! CorrelationScanner, Vulture, and NegRisk each source trade ideas from
+//! their own market view with no shared sense of how much risk the account
+//! is already carrying, so nothing stops them from collectively
+//! overcommitting capital or stacking correlated exposure. `PortfolioHealth`
+//! pulls every bot's open positions through the `AccountRetriever` trait and
+//! turns them into two margin-style health numbers, so a candidate trade
+//! from any bot can be admitted or rejected against the account's real
+//! remaining capacity instead of each bot sizing in isolation.
+
+use std::collections::HashMap;
+
+use pyo3::prelude::*;
+
+/// One open position: a signed size (positive = long, negative = short) in
+/// a single outcome token, at the price it was entered.
+#[derive(Debug, Clone)]
+pub struct Position {
+    pub token_id: String,
+    pub condition_id: String,
+    pub signed_size: f64,
+    pub entry_price: f64,
+}
+
+/// A source of open positions and current marks, implemented once per bot
+/// so `PortfolioHealth` can pull a consistent snapshot across all of them
+/// without any one bot needing to know about the others.
+pub trait AccountRetriever: Send + Sync {
+    /// This bot's currently open positions.
+    fn positions(&self) -> Vec<Position>;
+
+    /// Current oracle/mid mark for a token, if this retriever has one.
+    fn mark_price(&self, token_id: &str) -> Option<f64>;
+}
+
+/// A fixed snapshot of positions and marks. Useful for tests, and as the
+/// retriever backing the stateless Python API below.
+pub struct StaticPositions {
+    positions: Vec<Position>,
+    marks: HashMap<String, f64>,
+}
+
+impl StaticPositions {
+    pub fn new(positions: Vec<Position>, marks: HashMap<String, f64>) -> Self {
+        Self { positions, marks }
+    }
+}
+
+impl AccountRetriever for StaticPositions {
+    fn positions(&self) -> Vec<Position> {
+        self.positions.clone()
+    }
+
+    fn mark_price(&self, token_id: &str) -> Option<f64> {
+        self.marks.get(token_id).copied()
+    }
+}
+
+/// Margin weights applied to a condition's netted exposure.
+#[derive(Debug, Clone, Copy)]
+pub struct MarginWeights {
+    /// Weight applied at maintenance: close to 1.0, just enough haircut to
+    /// absorb stale marks before liquidation risk kicks in.
+    pub maintenance: f64,
+    /// Weight applied to long (non-negative) netted exposure for initial
+    /// health: discounts how much collateral credit a position earns.
+    pub initial_long_haircut: f64,
+    /// Weight applied to short (negative) netted exposure for initial
+    /// health: inflates how much liability a position counts as.
+    pub initial_short_inflation: f64,
+}
+
+impl Default for MarginWeights {
+    fn default() -> Self {
+        Self {
+            maintenance: 0.97,
+            initial_long_haircut: 0.85,
+            initial_short_inflation: 1.15,
+        }
+    }
+}
+
+/// Cross-bot margin engine: pulls positions from every registered bot and
+/// computes account health against a starting collateral balance.
+pub struct PortfolioHealth {
+    retrievers: Vec<Box<dyn AccountRetriever>>,
+    weights: MarginWeights,
+    starting_collateral: f64,
+}
+
+impl PortfolioHealth {
+    pub fn new(starting_collateral: f64, weights: MarginWeights) -> Self {
+        Self { retrievers: Vec::new(), weights, starting_collateral }
+    }
+
+    /// Register a bot's position source. Pulled fresh on every health call,
+    /// so a bot's latest fills are always reflected.
+    pub fn register(&mut self, retriever: Box<dyn AccountRetriever>) {
+        self.retrievers.push(retriever);
+    }
+
+    /// Net every retriever's positions by (condition_id, token_id), summing
+    /// signed size across bots that happen to hold the same outcome token.
+    fn net_by_condition(&self) -> HashMap<String, HashMap<String, f64>> {
+        let mut by_condition: HashMap<String, HashMap<String, f64>> = HashMap::new();
+        for retriever in &self.retrievers {
+            for pos in retriever.positions() {
+                *by_condition.entry(pos.condition_id).or_default().entry(pos.token_id).or_insert(0.0) += pos.signed_size;
+            }
+        }
+        by_condition
+    }
+
+    fn mark_for(&self, token_id: &str) -> Option<f64> {
+        self.retrievers.iter().find_map(|r| r.mark_price(token_id))
+    }
+
+    /// A single condition's contribution to account value. A lone leg is
+    /// marked at its current price like any other asset; two or more legs
+    /// in the same (mutually exclusive) condition are netted to the bounded
+    /// worst-case redemption value instead of summed gross notional, since
+    /// only one outcome can ever pay out.
+    fn condition_value(&self, legs: &HashMap<String, f64>) -> f64 {
+        if legs.len() <= 1 {
+            legs.iter().map(|(token, &size)| size * self.mark_for(token).unwrap_or(0.0)).sum()
+        } else {
+            // Whichever outcome wins, every other held leg pays zero, so the
+            // worst case is the smallest leg's own payout.
+            legs.values().copied().fold(f64::INFINITY, f64::min)
+        }
+    }
+
+    fn weighted_exposure(&self, weight_for: impl Fn(f64) -> f64) -> f64 {
+        self.net_by_condition().values().map(|legs| weight_for(self.condition_value(legs))).sum()
+    }
+
+    /// Account health using current marks and maintenance weights. Crossing
+    /// zero means the account is eligible for liquidation.
+    pub fn maintenance_health(&self) -> f64 {
+        self.starting_collateral + self.weighted_exposure(|v| v * self.weights.maintenance)
+    }
+
+    /// Account health using conservative haircut weights. New trades are
+    /// only admitted while this stays >= 0.
+    pub fn initial_health(&self) -> f64 {
+        let w = self.weights;
+        self.starting_collateral
+            + self.weighted_exposure(|v| if v >= 0.0 { v * w.initial_long_haircut } else { v * w.initial_short_inflation })
+    }
+
+    /// Collateral still free to back a new trade, floored at zero.
+    pub fn free_collateral(&self) -> f64 {
+        self.initial_health().max(0.0)
+    }
+
+    /// Would adding `candidate` on top of every registered bot's current
+    /// positions keep initial health at or above zero?
+    pub fn admits_trade(&self, candidate: &Position) -> bool {
+        let mut by_condition = self.net_by_condition();
+        *by_condition
+            .entry(candidate.condition_id.clone())
+            .or_default()
+            .entry(candidate.token_id.clone())
+            .or_insert(0.0) += candidate.signed_size;
+
+        let w = self.weights;
+        let projected: f64 = by_condition
+            .values()
+            .map(|legs| {
+                let v = self.condition_value(legs);
+                if v >= 0.0 { v * w.initial_long_haircut } else { v * w.initial_short_inflation }
+            })
+            .sum();
+
+        self.starting_collateral + projected >= 0.0
+    }
+}
+
+// ============ PyO3 Bindings ============
+
+/// Stateless snapshot API: each call passes the current positions and marks
+/// across every bot, since no single Rust process here holds the combined
+/// position ledger Python does.
+#[pyclass(name = "PortfolioHealth")]
+pub struct PyPortfolioHealth {
+    weights: MarginWeights,
+}
+
+impl PyPortfolioHealth {
+    fn build(&self, starting_collateral: f64, positions: Vec<(String, String, f64, f64)>, marks: HashMap<String, f64>) -> PortfolioHealth {
+        let positions = positions
+            .into_iter()
+            .map(|(token_id, condition_id, signed_size, entry_price)| Position {
+                token_id,
+                condition_id,
+                signed_size,
+                entry_price,
+            })
+            .collect();
+        let mut engine = PortfolioHealth::new(starting_collateral, self.weights);
+        engine.register(Box::new(StaticPositions::new(positions, marks)));
+        engine
+    }
+}
+
+#[pymethods]
+impl PyPortfolioHealth {
+    #[new]
+    #[pyo3(signature = (maintenance_weight=0.97, initial_long_haircut=0.85, initial_short_inflation=1.15))]
+    fn new(maintenance_weight: f64, initial_long_haircut: f64, initial_short_inflation: f64) -> Self {
+        Self {
+            weights: MarginWeights {
+                maintenance: maintenance_weight,
+                initial_long_haircut,
+                initial_short_inflation,
+            },
+        }
+    }
+
+    /// positions: list of (token_id, condition_id, signed_size, entry_price); marks: token_id -> price
+    fn maintenance_health(&self, starting_collateral: f64, positions: Vec<(String, String, f64, f64)>, marks: HashMap<String, f64>) -> f64 {
+        self.build(starting_collateral, positions, marks).maintenance_health()
+    }
+
+    fn initial_health(&self, starting_collateral: f64, positions: Vec<(String, String, f64, f64)>, marks: HashMap<String, f64>) -> f64 {
+        self.build(starting_collateral, positions, marks).initial_health()
+    }
+
+    fn free_collateral(&self, starting_collateral: f64, positions: Vec<(String, String, f64, f64)>, marks: HashMap<String, f64>) -> f64 {
+        self.build(starting_collateral, positions, marks).free_collateral()
+    }
+
+    /// candidate: (token_id, condition_id, signed_size, entry_price)
+    fn admits_trade(
+        &self,
+        starting_collateral: f64,
+        positions: Vec<(String, String, f64, f64)>,
+        marks: HashMap<String, f64>,
+        candidate: (String, String, f64, f64),
+    ) -> bool {
+        let engine = self.build(starting_collateral, positions, marks);
+        engine.admits_trade(&Position {
+            token_id: candidate.0,
+            condition_id: candidate.1,
+            signed_size: candidate.2,
+            entry_price: candidate.3,
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PortfolioHealth(maintenance={:.2}, initial_long={:.2}, initial_short={:.2})",
+            self.weights.maintenance, self.weights.initial_long_haircut, self.weights.initial_short_inflation
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn engine(starting_collateral: f64, positions: Vec<Position>, marks: HashMap<String, f64>) -> PortfolioHealth {
+        let mut engine = PortfolioHealth::new(starting_collateral, MarginWeights::default());
+        engine.register(Box::new(StaticPositions::new(positions, marks)));
+        engine
+    }
+
+    #[test]
+    fn single_leg_marks_to_market() {
+        let marks = HashMap::from([("yes".to_string(), 0.60)]);
+        let positions = vec![Position {
+            token_id: "yes".to_string(),
+            condition_id: "cond-a".to_string(),
+            signed_size: 100.0,
+            entry_price: 0.50,
+        }];
+        let e = engine(1_000.0, positions, marks);
+        // 100 * 0.60 * 0.97 maintenance weight
+        assert!((e.maintenance_health() - (1_000.0 + 100.0 * 0.60 * 0.97)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn hedged_condition_nets_to_worst_case_not_gross() {
+        // Long 100 YES and long 80 NO in the same condition: gross notional
+        // at mid would overstate risk. Worst case is min(100, 80) = 80.
+        let marks = HashMap::from([("yes".to_string(), 0.55), ("no".to_string(), 0.50)]);
+        let positions = vec![
+            Position { token_id: "yes".to_string(), condition_id: "cond-b".to_string(), signed_size: 100.0, entry_price: 0.50 },
+            Position { token_id: "no".to_string(), condition_id: "cond-b".to_string(), signed_size: 80.0, entry_price: 0.45 },
+        ];
+        let e = engine(1_000.0, positions, marks);
+        assert!((e.maintenance_health() - (1_000.0 + 80.0 * 0.97)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rejects_trade_that_would_sink_initial_health() {
+        let marks = HashMap::from([("yes".to_string(), 0.50)]);
+        let e = engine(100.0, Vec::new(), marks);
+        let oversized = Position {
+            token_id: "yes".to_string(),
+            condition_id: "cond-c".to_string(),
+            signed_size: -1_000.0, // a big short with no offsetting collateral
+            entry_price: 0.50,
+        };
+        assert!(!e.admits_trade(&oversized));
+    }
+
+    #[test]
+    fn admits_trade_within_free_collateral() {
+        let marks = HashMap::from([("yes".to_string(), 0.50)]);
+        let e = engine(1_000.0, Vec::new(), marks);
+        let modest = Position {
+            token_id: "yes".to_string(),
+            condition_id: "cond-d".to_string(),
+            signed_size: 50.0,
+            entry_price: 0.50,
+        };
+        assert!(e.admits_trade(&modest));
+    }
+
+    #[test]
+    fn free_collateral_floors_at_zero() {
+        let marks = HashMap::from([("yes".to_string(), 0.10)]);
+        let positions = vec![Position {
+            token_id: "yes".to_string(),
+            condition_id: "cond-e".to_string(),
+            signed_size: -500.0,
+            entry_price: 0.50,
+        }];
+        let e = engine(1.0, positions, marks);
+        assert_eq!(e.free_collateral(), 0.0);
+    }
+}
@@ -0,0 +1,288 @@
+//! Quoting Module - Layered Market-Making Quote Engine
+//!
+//! Derives a reference price from book depth (not just the top level) and
+//! lays out a ladder of bid/ask quotes around it, so quotes skew away from
+//! thin top-of-book liquidity.
+
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::orderbook::{Orderbook, Side};
+
+/// Layer spacing mode for the quote ladder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Spacing {
+    /// Layer i offset = spread_bps * multiplier * (i + 1)
+    Arithmetic,
+    /// Layer i offset = spread_bps * multiplier^(i + 1)
+    Geometric,
+}
+
+/// Configuration for the quote ladder
+#[derive(Debug, Clone)]
+#[pyclass]
+pub struct QuoteEngineConfig {
+    /// Number of layers per side
+    #[pyo3(get, set)]
+    pub levels: usize,
+    /// Base spread in bps, scaled per layer by `spacing_multiplier` (the
+    /// first layer sits at `spread_bps * spacing_multiplier` in both modes)
+    #[pyo3(get, set)]
+    pub spread_bps: f64,
+    /// Per-layer spacing multiplier (used by geometric spacing, and as the
+    /// arithmetic step multiplier otherwise)
+    #[pyo3(get, set)]
+    pub spacing_multiplier: f64,
+    /// Use geometric (vs. arithmetic) layer spacing
+    #[pyo3(get, set)]
+    pub geometric: bool,
+    /// Base quantity for the innermost layer
+    #[pyo3(get, set)]
+    pub base_qty: f64,
+    /// Per-layer quantity multiplier: qty_i = base_qty * qty_multiplier^i
+    #[pyo3(get, set)]
+    pub qty_multiplier: f64,
+    /// Notional to fill into the book when computing the depth-aware reference price
+    #[pyo3(get, set)]
+    pub source_depth_notional: f64,
+    /// Minimum move in the reference price (bps) required to requote
+    #[pyo3(get, set)]
+    pub requote_threshold_bps: f64,
+}
+
+#[pymethods]
+impl QuoteEngineConfig {
+    #[new]
+    fn new() -> Self {
+        Self {
+            levels: 3,
+            spread_bps: 25.0,
+            spacing_multiplier: 1.5,
+            geometric: false,
+            base_qty: 10.0,
+            qty_multiplier: 1.0,
+            source_depth_notional: 100.0,
+            requote_threshold_bps: 5.0,
+        }
+    }
+}
+
+impl QuoteEngineConfig {
+    fn spacing(&self) -> Spacing {
+        if self.geometric {
+            Spacing::Geometric
+        } else {
+            Spacing::Arithmetic
+        }
+    }
+}
+
+/// A single quote in the ladder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[pyclass]
+pub struct Quote {
+    #[pyo3(get)]
+    pub side: String,
+    #[pyo3(get)]
+    pub price: f64,
+    #[pyo3(get)]
+    pub size: f64,
+}
+
+#[pymethods]
+impl Quote {
+    fn __repr__(&self) -> String {
+        format!("Quote({} {:.4} x {})", self.side, self.price, self.size)
+    }
+}
+
+/// Layered market-making quote engine
+#[derive(Debug, Clone)]
+#[pyclass]
+pub struct QuoteEngine {
+    config: QuoteEngineConfig,
+    last_mid: Option<f64>,
+}
+
+#[pymethods]
+impl QuoteEngine {
+    #[new]
+    fn new(config: QuoteEngineConfig) -> Self {
+        Self { config, last_mid: None }
+    }
+
+    /// Compute the depth-aware reference price: the VWAP to fill
+    /// `source_depth_notional` into the book, averaged across both sides.
+    fn reference_price(&self, book: &crate::orderbook::PyOrderbook) -> Option<f64> {
+        self.reference_price_inner(&book.inner)
+    }
+
+    /// Generate a full bid/ask quote ladder off the current book
+    fn generate_quotes(&self, book: &crate::orderbook::PyOrderbook) -> Vec<Quote> {
+        self.generate_quotes_inner(&book.inner)
+    }
+
+    /// Generate quotes only if the reference price has moved beyond
+    /// `requote_threshold_bps` since the last call; returns `None` otherwise.
+    fn requote(&mut self, book: &crate::orderbook::PyOrderbook) -> Option<Vec<Quote>> {
+        let reference = self.reference_price_inner(&book.inner)?;
+
+        if let Some(prev) = self.last_mid {
+            let move_bps = ((reference - prev) / prev).abs() * 10_000.0;
+            if move_bps < self.config.requote_threshold_bps {
+                return None;
+            }
+        }
+
+        self.last_mid = Some(reference);
+        Some(self.quotes_at(reference))
+    }
+}
+
+impl QuoteEngine {
+    fn reference_price_inner(&self, book: &std::sync::Arc<Orderbook>) -> Option<f64> {
+        let mid = book.mid_price()?;
+        let size = self.config.source_depth_notional / mid.max(1e-9);
+
+        let bid_vwap = book.simulate_take(Side::Ask, size, None);
+        let ask_vwap = book.simulate_take(Side::Bid, size, None);
+
+        match (bid_vwap.filled > 0.0, ask_vwap.filled > 0.0) {
+            (true, true) => Some((bid_vwap.avg_price + ask_vwap.avg_price) / 2.0),
+            (true, false) => Some(bid_vwap.avg_price),
+            (false, true) => Some(ask_vwap.avg_price),
+            (false, false) => Some(mid),
+        }
+    }
+
+    fn layer_offset_bps(&self, i: usize) -> f64 {
+        match self.config.spacing() {
+            Spacing::Arithmetic => self.config.spread_bps * self.config.spacing_multiplier * (i as f64 + 1.0),
+            Spacing::Geometric => self.config.spread_bps * self.config.spacing_multiplier.powi(i as i32 + 1),
+        }
+    }
+
+    fn layer_qty(&self, i: usize) -> f64 {
+        self.config.base_qty * self.config.qty_multiplier.powi(i as i32)
+    }
+
+    fn quotes_at(&self, reference: f64) -> Vec<Quote> {
+        let mut quotes = Vec::with_capacity(self.config.levels * 2);
+
+        for i in 0..self.config.levels {
+            let offset = reference * self.layer_offset_bps(i) / 10_000.0;
+            let qty = self.layer_qty(i);
+
+            quotes.push(Quote {
+                side: "bid".to_string(),
+                price: reference - offset,
+                size: qty,
+            });
+            quotes.push(Quote {
+                side: "ask".to_string(),
+                price: reference + offset,
+                size: qty,
+            });
+        }
+
+        quotes
+    }
+
+    fn generate_quotes_inner(&self, book: &std::sync::Arc<Orderbook>) -> Vec<Quote> {
+        match self.reference_price_inner(book) {
+            Some(reference) => self.quotes_at(reference),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::OrderbookDelta;
+
+    fn book_with_liquidity() -> std::sync::Arc<Orderbook> {
+        let book = std::sync::Arc::new(Orderbook::new("tok".to_string()));
+        book.apply_delta(&OrderbookDelta { price: 0.49, size: 1_000.0, side: "bid".to_string(), order_count: None });
+        book.apply_delta(&OrderbookDelta { price: 0.51, size: 1_000.0, side: "ask".to_string(), order_count: None });
+        book
+    }
+
+    fn default_config() -> QuoteEngineConfig {
+        QuoteEngineConfig {
+            levels: 3,
+            spread_bps: 25.0,
+            spacing_multiplier: 1.5,
+            geometric: false,
+            base_qty: 10.0,
+            qty_multiplier: 1.0,
+            source_depth_notional: 100.0,
+            requote_threshold_bps: 5.0,
+        }
+    }
+
+    #[test]
+    fn reference_price_falls_back_to_mid_with_no_depth() {
+        let book = std::sync::Arc::new(Orderbook::new("tok".to_string()));
+        book.apply_delta(&OrderbookDelta { price: 0.49, size: 0.0001, side: "bid".to_string(), order_count: None });
+        book.apply_delta(&OrderbookDelta { price: 0.51, size: 0.0001, side: "ask".to_string(), order_count: None });
+
+        let engine = QuoteEngine::new(default_config());
+        let reference = engine.reference_price_inner(&book).unwrap();
+        assert!((reference - book.mid_price().unwrap()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn generate_quotes_produces_levels_symmetric_around_reference() {
+        let book = book_with_liquidity();
+        let engine = QuoteEngine::new(default_config());
+        let quotes = engine.generate_quotes_inner(&book);
+
+        assert_eq!(quotes.len(), 6); // 3 levels * (bid + ask)
+        let reference = engine.reference_price_inner(&book).unwrap();
+        let bid0 = quotes.iter().find(|q| q.side == "bid").unwrap();
+        let ask0 = quotes.iter().find(|q| q.side == "ask").unwrap();
+        assert!(bid0.price < reference);
+        assert!(ask0.price > reference);
+    }
+
+    #[test]
+    fn arithmetic_spacing_grows_linearly_between_layers() {
+        let config = default_config();
+        let engine = QuoteEngine::new(config.clone());
+        let offset0 = engine.layer_offset_bps(0);
+        let offset1 = engine.layer_offset_bps(1);
+        assert!((offset1 - offset0 - config.spread_bps * config.spacing_multiplier).abs() < 1e-9);
+    }
+
+    #[test]
+    fn geometric_spacing_grows_multiplicatively_between_layers() {
+        let mut config = default_config();
+        config.geometric = true;
+        let engine = QuoteEngine::new(config.clone());
+        let offset0 = engine.layer_offset_bps(0);
+        let offset1 = engine.layer_offset_bps(1);
+        assert!((offset1 / offset0 - config.spacing_multiplier).abs() < 1e-9);
+    }
+
+    #[test]
+    fn requote_suppresses_updates_below_threshold() {
+        let book = book_with_liquidity();
+        let mut engine = QuoteEngine::new(default_config());
+
+        assert!(engine.requote(&book).is_some());
+        // Mid hasn't moved at all, so a second call should be suppressed.
+        assert!(engine.requote(&book).is_none());
+    }
+
+    #[test]
+    fn requote_fires_again_once_mid_moves_past_threshold() {
+        let book = book_with_liquidity();
+        let mut engine = QuoteEngine::new(default_config());
+        assert!(engine.requote(&book).is_some());
+
+        book.apply_delta(&OrderbookDelta { price: 0.60, size: 1_000.0, side: "bid".to_string(), order_count: None });
+        book.apply_delta(&OrderbookDelta { price: 0.62, size: 1_000.0, side: "ask".to_string(), order_count: None });
+        assert!(engine.requote(&book).is_some());
+    }
+}
@@ -1,285 +1,982 @@
 //! Signer Module - EIP-712 Signing for Polymarket CTF Orders
-//! 
+//!
 //! Implements order signing compatible with Polymarket's CTF Exchange:
 //! - EIP-712 typed data hashing
 //! - Order struct serialization
-//! - Signature generation
+//! - Pluggable signing backends (in-memory key, Ledger hardware wallet, ...)
+//!
+//! Signing is abstracted behind `OrderSignerBackend` so that `PolymarketSigner`
+//! never has to know whether the private key lives in memory or on an HSM -
+//! it only ever asks a backend to sign a message hash and hand back a
+//! signature.
 
+use async_trait::async_trait;
+use ethers::core::types::transaction::eip2718::TypedTransaction;
+use ethers::core::types::{Address, Eip1559TransactionRequest, Signature, H256, U256};
+use ethers::providers::{Http, Middleware, Provider};
+use ethers::signers::coins_bip39::English;
+use ethers::signers::{HDPath, Ledger, LocalWallet, MnemonicBuilder, Signer};
 use pyo3::prelude::*;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Keccak256};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use zeroize::Zeroizing;
 
-/// EIP-712 Domain for Polymarket CTF Exchange
-const DOMAIN_NAME: &str = "Polymarket CTF Exchange";
-const DOMAIN_VERSION: &str = "1";
-const CHAIN_ID: u64 = 137; // Polygon mainnet
+/// Polymarket Chain ID (Polygon)
+const CHAIN_ID: u64 = 137;
+
+/// CTF Exchange Contract
+const CTF_EXCHANGE: &str = "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E";
+
+/// NegRisk CTF Exchange
+const NEGRISK_CTF_EXCHANGE: &str = "0xC5d563A36AE78145C45a50134d48A1215220f80a";
 
 /// Order side
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum OrderSide {
+#[repr(u8)]
+pub enum Side {
     Buy = 0,
     Sell = 1,
 }
 
-/// Order type
+/// Signature type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
 pub enum SignatureType {
-    EOA = 0,
-    PolyProxy = 1,
-    PolyGnosisSafe = 2,
+    Eoa = 0,
+    Poly = 1,
+    PolyProxy = 2,
 }
 
-/// A Polymarket CTF Order
+/// Order structure matching Polymarket CLOB
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Order {
-    pub salt: String,
-    pub maker: String,
-    pub signer: String,
-    pub taker: String,
-    pub token_id: String,
-    pub maker_amount: String,
-    pub taker_amount: String,
-    pub expiration: String,
-    pub nonce: String,
-    pub fee_rate_bps: String,
-    pub side: OrderSide,
+    pub salt: U256,
+    pub maker: Address,
+    pub signer: Address,
+    pub taker: Address,
+    pub token_id: U256,
+    pub maker_amount: U256,
+    pub taker_amount: U256,
+    pub expiration: U256,
+    pub nonce: U256,
+    pub fee_rate_bps: U256,
+    pub side: Side,
     pub signature_type: SignatureType,
 }
 
-impl Order {
-    /// Create a new order
-    pub fn new(
-        maker: String,
-        token_id: String,
-        side: OrderSide,
-        price: f64,
-        size: f64,
+/// Signed order ready for submission
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedOrder {
+    pub order: Order,
+    pub signature: String,
+}
+
+impl SignedOrder {
+    /// Render this order the way the Polymarket CLOB REST endpoint expects
+    /// it, rather than ethers' default `Debug`/hex representation: decimal
+    /// string uint256s, lowercase `0x` addresses, `"BUY"`/`"SELL"` side, and
+    /// the integer `signatureType` discriminant, with `signature` alongside.
+    /// Directly POST-able with no Python-side reshaping.
+    pub fn to_clob_json(&self) -> Result<String, SignerError> {
+        let wire = ClobSignedOrder {
+            order: ClobOrder::from(&self.order),
+            signature: self.signature.clone(),
+        };
+        serde_json::to_string(&wire).map_err(|e| SignerError::Serialization(e.to_string()))
+    }
+}
+
+/// Thin serde wrapper that renders a `U256` as a base-10 decimal string
+/// instead of ethers' default hex, matching what the CLOB REST endpoint
+/// expects for `salt`/`tokenId`/amount/`nonce`/`feeRateBps` fields.
+struct HexOrDecimalU256(U256);
+
+impl Serialize for HexOrDecimalU256 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+/// CLOB wire format for an `Order`: decimal-string uint256s, lowercase hex
+/// addresses, `"BUY"`/`"SELL"` side, and an integer `signatureType`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ClobOrder {
+    salt: HexOrDecimalU256,
+    maker: String,
+    signer: String,
+    taker: String,
+    token_id: HexOrDecimalU256,
+    maker_amount: HexOrDecimalU256,
+    taker_amount: HexOrDecimalU256,
+    expiration: HexOrDecimalU256,
+    nonce: HexOrDecimalU256,
+    fee_rate_bps: HexOrDecimalU256,
+    side: &'static str,
+    signature_type: u8,
+}
+
+impl From<&Order> for ClobOrder {
+    fn from(order: &Order) -> Self {
+        Self {
+            salt: HexOrDecimalU256(order.salt),
+            maker: lowercase_hex_address(order.maker),
+            signer: lowercase_hex_address(order.signer),
+            taker: lowercase_hex_address(order.taker),
+            token_id: HexOrDecimalU256(order.token_id),
+            maker_amount: HexOrDecimalU256(order.maker_amount),
+            taker_amount: HexOrDecimalU256(order.taker_amount),
+            expiration: HexOrDecimalU256(order.expiration),
+            nonce: HexOrDecimalU256(order.nonce),
+            fee_rate_bps: HexOrDecimalU256(order.fee_rate_bps),
+            side: match order.side {
+                Side::Buy => "BUY",
+                Side::Sell => "SELL",
+            },
+            signature_type: order.signature_type as u8,
+        }
+    }
+}
+
+/// CLOB wire format for a `SignedOrder`: the order fields alongside the
+/// detached signature hex.
+#[derive(Serialize)]
+struct ClobSignedOrder {
+    #[serde(flatten)]
+    order: ClobOrder,
+    signature: String,
+}
+
+fn lowercase_hex_address(addr: Address) -> String {
+    format!("{:?}", addr).to_lowercase()
+}
+
+/// EIP-712 Domain Separator components
+struct EIP712Domain {
+    name: String,
+    version: String,
+    chain_id: U256,
+    verifying_contract: Address,
+}
+
+impl EIP712Domain {
+    fn new(is_negrisk: bool) -> Self {
+        let contract = if is_negrisk {
+            NEGRISK_CTF_EXCHANGE
+        } else {
+            CTF_EXCHANGE
+        };
+
+        Self {
+            name: "Polymarket CTF Exchange".to_string(),
+            version: "1".to_string(),
+            chain_id: U256::from(CHAIN_ID),
+            verifying_contract: Address::from_str(contract).unwrap(),
+        }
+    }
+
+    fn separator_hash(&self) -> H256 {
+        let type_hash = keccak256(
+            b"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)",
+        );
+
+        let mut data = Vec::new();
+        data.extend_from_slice(type_hash.as_slice());
+        data.extend_from_slice(keccak256(self.name.as_bytes()).as_slice());
+        data.extend_from_slice(keccak256(self.version.as_bytes()).as_slice());
+        data.extend_from_slice(&u256_to_bytes32(self.chain_id));
+        data.extend_from_slice(self.verifying_contract.as_bytes());
+
+        H256::from_slice(&keccak256(&data))
+    }
+}
+
+/// A pluggable signing backend. `PolymarketSigner` only ever talks to this
+/// trait, so the private key material can live in-process (`LocalWallet`) or
+/// stay on a hardware device (`Ledger`) without either caller or signer
+/// caring which.
+#[async_trait]
+pub trait OrderSignerBackend: Send + Sync {
+    /// Sign a 32-byte EIP-712 message hash, returning the 65-byte `r‖s‖v`
+    /// signature.
+    async fn sign_hash(&self, hash: H256) -> Result<Signature, SignerError>;
+
+    /// Sign a raw on-chain transaction (e.g. the CTF Exchange's
+    /// `incrementNonce`), so cancellation can be submitted for real instead
+    /// of only mutating the local atomic.
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, SignerError>;
+
+    /// The address this backend signs on behalf of.
+    fn address(&self) -> Address;
+}
+
+/// Backend that signs with an in-memory private key (the original,
+/// un-hardware-backed behavior).
+pub struct LocalWalletBackend {
+    wallet: LocalWallet,
+}
+
+impl LocalWalletBackend {
+    pub fn new(private_key: &str) -> Result<Self, SignerError> {
+        // Copy into a zeroizing buffer so the stripped key material is
+        // scrubbed from memory as soon as `LocalWallet` has parsed it,
+        // rather than lingering on the heap for the life of the process.
+        let key = Zeroizing::new(
+            private_key
+                .strip_prefix("0x")
+                .unwrap_or(private_key)
+                .to_string(),
+        );
+        let wallet = LocalWallet::from_str(&key)
+            .map_err(|e| SignerError::InvalidKey(e.to_string()))?
+            .with_chain_id(CHAIN_ID);
+
+        Ok(Self { wallet })
+    }
+
+    /// Derive the signing key via BIP-32 from a mnemonic phrase along
+    /// `m/44'/60'/0'/0/{account_index}`, so a sub-account key can be
+    /// re-derived from (seed, index) instead of being persisted to disk.
+    pub fn from_mnemonic(phrase: &str, account_index: u32) -> Result<Self, SignerError> {
+        // Same rationale as `new`: a seed phrase is as sensitive as a raw
+        // key, so don't let an extra copy of it outlive this call.
+        let phrase = Zeroizing::new(phrase.to_string());
+        let derivation_path = format!("m/44'/60'/0'/0/{}", account_index);
+        let wallet = MnemonicBuilder::<English>::default()
+            .phrase(phrase.as_str())
+            .derivation_path(&derivation_path)
+            .map_err(|e| SignerError::InvalidKey(e.to_string()))?
+            .build()
+            .map_err(|e| SignerError::InvalidKey(e.to_string()))?
+            .with_chain_id(CHAIN_ID);
+
+        Ok(Self { wallet })
+    }
+}
+
+#[async_trait]
+impl OrderSignerBackend for LocalWalletBackend {
+    async fn sign_hash(&self, hash: H256) -> Result<Signature, SignerError> {
+        self.wallet
+            .sign_hash(hash)
+            .map_err(|e| SignerError::SigningError(e.to_string()))
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, SignerError> {
+        self.wallet
+            .sign_transaction(tx)
+            .await
+            .map_err(|e| SignerError::SigningError(e.to_string()))
+    }
+
+    fn address(&self) -> Address {
+        self.wallet.address()
+    }
+}
+
+/// Backend that drives a Ledger Nano over the Ethereum app, so the maker's
+/// private key never leaves the device.
+pub struct LedgerBackend {
+    ledger: Ledger,
+}
+
+impl LedgerBackend {
+    /// Open the device and select the Ledger Live path
+    /// `m/44'/60'/{account_index}'/0/0`.
+    pub async fn new(account_index: usize) -> Result<Self, SignerError> {
+        let ledger = Ledger::new(HDPath::LedgerLive(account_index), CHAIN_ID)
+            .await
+            .map_err(|e| SignerError::Hardware(e.to_string()))?;
+
+        Ok(Self { ledger })
+    }
+}
+
+#[async_trait]
+impl OrderSignerBackend for LedgerBackend {
+    async fn sign_hash(&self, hash: H256) -> Result<Signature, SignerError> {
+        // Submits the final EIP-712 message hash to the device for
+        // on-device signing; the operator confirms on the Ledger screen.
+        self.ledger
+            .sign_hash(hash)
+            .await
+            .map_err(|e| SignerError::Hardware(e.to_string()))
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, SignerError> {
+        self.ledger
+            .sign_transaction(tx)
+            .await
+            .map_err(|e| SignerError::Hardware(e.to_string()))
+    }
+
+    fn address(&self) -> Address {
+        self.ledger.address()
+    }
+}
+
+/// Main signer for Polymarket orders. Generic over the signing backend so
+/// callers can swap an in-memory key for a hardware wallet without touching
+/// order-building or EIP-712 logic.
+pub struct PolymarketSigner {
+    backend: Arc<dyn OrderSignerBackend>,
+    nonce: AtomicU64,
+    is_negrisk: bool,
+    /// Proxy/funder wallet that the order should be attributed to, if this
+    /// signer trades on behalf of a Polymarket proxy wallet rather than
+    /// directly as the EOA that signs.
+    proxy: Option<ProxyWallet>,
+    /// RPC provider used to read/write the authoritative on-chain nonce.
+    /// `None` until `with_provider` is called, in which case the nonce
+    /// stays purely local (the original behavior).
+    provider: Option<Arc<Provider<Http>>>,
+}
+
+/// A Polymarket proxy (gasless) wallet: the on-chain address that actually
+/// custodies USDC/CTF balances, distinct from the EOA that produces the
+/// EIP-712 signature.
+#[derive(Debug, Clone, Copy)]
+struct ProxyWallet {
+    funder: Address,
+    signature_type: SignatureType,
+}
+
+impl PolymarketSigner {
+    /// Build a signer backed by an in-memory private key.
+    pub fn new(private_key: &str, is_negrisk: bool) -> Result<Self, SignerError> {
+        let backend = LocalWalletBackend::new(private_key)?;
+        Ok(Self::with_backend(Arc::new(backend), is_negrisk))
+    }
+
+    /// Build a signer backed by a Ledger hardware wallet at the given
+    /// account index.
+    pub async fn with_ledger(account_index: usize, is_negrisk: bool) -> Result<Self, SignerError> {
+        let backend = LedgerBackend::new(account_index).await?;
+        Ok(Self::with_backend(Arc::new(backend), is_negrisk))
+    }
+
+    /// Build a signer whose key is deterministically re-derived from a
+    /// BIP-39 mnemonic and account index rather than stored, so many
+    /// sub-accounts can run off one seed phrase without a key file each.
+    /// Each derived account gets its own `PolymarketSigner` instance (and
+    /// thus its own `nonce`), so `next_nonce`/`cancel_all_orders` on one
+    /// sub-account never touch another's.
+    pub fn from_mnemonic(
+        phrase: &str,
+        account_index: u32,
+        is_negrisk: bool,
+    ) -> Result<Self, SignerError> {
+        let backend = LocalWalletBackend::from_mnemonic(phrase, account_index)?;
+        Ok(Self::with_backend(Arc::new(backend), is_negrisk))
+    }
+
+    /// Build a signer from any `OrderSignerBackend`.
+    pub fn with_backend(backend: Arc<dyn OrderSignerBackend>, is_negrisk: bool) -> Self {
+        Self {
+            backend,
+            nonce: AtomicU64::new(0),
+            is_negrisk,
+            proxy: None,
+            provider: None,
+        }
+    }
+
+    /// Attach an RPC provider so `sync_nonce`/`cancel_all_on_chain` can read
+    /// and mutate the authoritative on-chain nonce instead of trusting the
+    /// local atomic alone across restarts or out-of-band cancellations.
+    pub fn with_provider(mut self, provider: Arc<Provider<Http>>) -> Self {
+        self.provider = Some(provider);
+        self
+    }
+
+    fn exchange_address(&self) -> Address {
+        let contract = if self.is_negrisk {
+            NEGRISK_CTF_EXCHANGE
+        } else {
+            CTF_EXCHANGE
+        };
+        Address::from_str(contract).unwrap()
+    }
+
+    /// Read the maker's current nonce from the CTF Exchange contract on
+    /// Polygon and adopt it as the local nonce, giving the nonce counter a
+    /// single authoritative source instead of an in-memory value that
+    /// drifts across restarts.
+    pub async fn sync_nonce(&self) -> Result<u64, SignerError> {
+        let provider = self.provider.as_ref().ok_or(SignerError::NoProvider)?;
+
+        let mut calldata = nonces_selector().to_vec();
+        calldata.extend_from_slice(&address_to_bytes32(self.address()));
+
+        let tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .to(self.exchange_address())
+            .data(calldata)
+            .into();
+
+        let result = provider
+            .call(&tx, None)
+            .await
+            .map_err(|e| SignerError::ChainError(e.to_string()))?;
+
+        let nonce = U256::from_big_endian(&result).as_u64();
+        self.set_nonce(nonce);
+        Ok(nonce)
+    }
+
+    /// Submit the CTF Exchange's `incrementNonce` transaction, so
+    /// cancellation is actually effective against the matching engine rather
+    /// than only mutating the local atomic, then refresh the local nonce
+    /// from the confirmed on-chain value.
+    pub async fn cancel_all_on_chain(&self) -> Result<(), SignerError> {
+        let provider = self.provider.as_ref().ok_or(SignerError::NoProvider)?;
+
+        let mut tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .to(self.exchange_address())
+            .from(self.address())
+            .data(increment_nonce_selector().to_vec())
+            .into();
+
+        provider
+            .fill_transaction(&mut tx, None)
+            .await
+            .map_err(|e| SignerError::ChainError(e.to_string()))?;
+
+        let signature = self.backend.sign_transaction(&tx).await?;
+        let raw_tx = tx.rlp_signed(&signature);
+
+        provider
+            .send_raw_transaction(raw_tx)
+            .await
+            .map_err(|e| SignerError::ChainError(e.to_string()))?
+            .await
+            .map_err(|e| SignerError::ChainError(e.to_string()))?;
+
+        self.sync_nonce().await?;
+        Ok(())
+    }
+
+    /// Attribute orders signed by this EOA to a Polymarket proxy (gasless)
+    /// wallet instead: `maker` becomes `funder` while `signer` stays the EOA
+    /// that actually produces the signature. `signature_type` must be
+    /// `Poly` or `PolyProxy` - the EIP-712 struct hash and domain are
+    /// unaffected, since they sign `maker`/`signer` exactly as given.
+    pub fn with_proxy(mut self, funder: Address, signature_type: SignatureType) -> Result<Self, SignerError> {
+        if signature_type == SignatureType::Eoa {
+            return Err(SignerError::InvalidProxySignatureType);
+        }
+        self.proxy = Some(ProxyWallet {
+            funder,
+            signature_type,
+        });
+        Ok(self)
+    }
+
+    /// Get signer address
+    pub fn address(&self) -> Address {
+        self.backend.address()
+    }
+
+    /// Generate next nonce
+    pub fn next_nonce(&self) -> U256 {
+        U256::from(self.nonce.fetch_add(1, Ordering::SeqCst))
+    }
+
+    /// Set nonce (for synchronization with chain state)
+    pub fn set_nonce(&self, nonce: u64) {
+        self.nonce.store(nonce, Ordering::SeqCst);
+    }
+
+    /// Create and sign a limit order
+    pub async fn create_limit_order(
+        &self,
+        token_id: &str,
+        price: Decimal,
+        size: Decimal,
+        side: Side,
         expiration_secs: u64,
-    ) -> Self {
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        
-        // Generate random salt
-        let salt = format!("{:016x}{:016x}", rand_u64(), rand_u64());
-        
+    ) -> Result<SignedOrder, SignerError> {
+        let token_id_u256 = parse_u256(token_id)
+            .map_err(|e| SignerError::InvalidTokenId(e.to_string()))?;
+
         // Calculate amounts based on side
+        // For BUY: maker pays USDC, receives tokens
+        // For SELL: maker pays tokens, receives USDC
         let (maker_amount, taker_amount) = match side {
-            OrderSide::Buy => {
-                // Buying: paying USDC, receiving shares
-                let usdc_amount = (price * size * 1_000_000.0) as u64; // 6 decimals
-                let share_amount = (size * 1_000_000.0) as u64;
-                (usdc_amount.to_string(), share_amount.to_string())
+            Side::Buy => {
+                let usdc_amount = (price * size * Decimal::from(1_000_000))
+                    .to_string()
+                    .parse::<u128>()
+                    .unwrap_or(0);
+                let token_amount = (size * Decimal::from(1_000_000))
+                    .to_string()
+                    .parse::<u128>()
+                    .unwrap_or(0);
+                (U256::from(usdc_amount), U256::from(token_amount))
             }
-            OrderSide::Sell => {
-                // Selling: paying shares, receiving USDC
-                let share_amount = (size * 1_000_000.0) as u64;
-                let usdc_amount = (price * size * 1_000_000.0) as u64;
-                (share_amount.to_string(), usdc_amount.to_string())
+            Side::Sell => {
+                let token_amount = (size * Decimal::from(1_000_000))
+                    .to_string()
+                    .parse::<u128>()
+                    .unwrap_or(0);
+                let usdc_amount = (price * size * Decimal::from(1_000_000))
+                    .to_string()
+                    .parse::<u128>()
+                    .unwrap_or(0);
+                (U256::from(token_amount), U256::from(usdc_amount))
             }
         };
 
-        Self {
-            salt,
-            maker: maker.clone(),
-            signer: maker,
-            taker: "0x0000000000000000000000000000000000000000".to_string(),
-            token_id,
+        let current_time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let (maker, signature_type) = match self.proxy {
+            Some(proxy) => (proxy.funder, proxy.signature_type),
+            None => (self.address(), SignatureType::Eoa),
+        };
+
+        let order = Order {
+            salt: generate_salt(),
+            maker,
+            signer: self.address(),
+            taker: Address::zero(),
+            token_id: token_id_u256,
             maker_amount,
             taker_amount,
-            expiration: (now + expiration_secs).to_string(),
-            nonce: "0".to_string(),
-            fee_rate_bps: "0".to_string(),
+            expiration: U256::from(current_time + expiration_secs),
+            nonce: self.next_nonce(),
+            fee_rate_bps: U256::zero(),
             side,
-            signature_type: SignatureType::EOA,
-        }
+            signature_type,
+        };
+
+        let signature = self.sign_order(&order).await?;
+
+        Ok(SignedOrder { order, signature })
+    }
+
+    /// Sign an order using EIP-712, delegating the actual key operation to
+    /// `self.backend` so the caller never needs to know whether the key is
+    /// in memory or on a Ledger.
+    async fn sign_order(&self, order: &Order) -> Result<String, SignerError> {
+        let domain = EIP712Domain::new(self.is_negrisk);
+        let domain_separator = domain.separator_hash();
+        let struct_hash = hash_order(order);
+
+        // Final message hash: \x19\x01 || domainSeparator || structHash
+        let mut message = Vec::new();
+        message.push(0x19);
+        message.push(0x01);
+        message.extend_from_slice(domain_separator.as_bytes());
+        message.extend_from_slice(struct_hash.as_bytes());
+
+        let message_hash = H256::from_slice(&keccak256(&message));
+
+        // Sign the hash via whichever backend this signer was built with
+        let signature = self.backend.sign_hash(message_hash).await?;
+
+        // Return signature as hex string with 0x prefix
+        Ok(format!("0x{}", hex::encode(signature.to_vec())))
+    }
+
+    /// Cancel an order (by incrementing nonce)
+    pub fn cancel_all_orders(&self) {
+        // In Polymarket, incrementing the nonce cancels all orders with lower nonces
+        self.next_nonce();
     }
 }
 
-/// Simple pseudo-random u64 for salt generation
-fn rand_u64() -> u64 {
-    use std::time::{SystemTime, UNIX_EPOCH};
-    let nanos = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .subsec_nanos() as u64;
-    nanos.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407)
+/// Generate a random salt for order uniqueness. Fills the full 256 bits
+/// from a CSPRNG rather than deriving from wall-clock time, so concurrent
+/// HFT order signing can never produce two orders with the same salt (and
+/// thus the same hash, which the exchange would reject as a replay).
+fn generate_salt() -> U256 {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    U256::from_big_endian(&bytes)
 }
 
-/// Keccak256 hash helper
+/// EIP-712 type hash for the `Order` struct. Field names and order here
+/// must exactly match the encoding order in `hash_order`, or the struct
+/// hash (and every signature over it) is silently wrong.
+fn order_type_hash() -> [u8; 32] {
+    keccak256(
+        b"Order(uint256 salt,address maker,address signer,address taker,uint256 tokenId,uint256 makerAmount,uint256 takerAmount,uint256 expiration,uint256 nonce,uint256 feeRateBps,uint8 side,uint8 signatureType)",
+    )
+}
+
+/// Hash an `Order` per its EIP-712 struct encoding.
+fn hash_order(order: &Order) -> H256 {
+    let mut order_data = Vec::new();
+    order_data.extend_from_slice(&order_type_hash());
+    order_data.extend_from_slice(&u256_to_bytes32(order.salt));
+    order_data.extend_from_slice(&address_to_bytes32(order.maker));
+    order_data.extend_from_slice(&address_to_bytes32(order.signer));
+    order_data.extend_from_slice(&address_to_bytes32(order.taker));
+    order_data.extend_from_slice(&u256_to_bytes32(order.token_id));
+    order_data.extend_from_slice(&u256_to_bytes32(order.maker_amount));
+    order_data.extend_from_slice(&u256_to_bytes32(order.taker_amount));
+    order_data.extend_from_slice(&u256_to_bytes32(order.expiration));
+    order_data.extend_from_slice(&u256_to_bytes32(order.nonce));
+    order_data.extend_from_slice(&u256_to_bytes32(order.fee_rate_bps));
+    order_data.extend_from_slice(&u8_to_bytes32(order.side as u8));
+    order_data.extend_from_slice(&u8_to_bytes32(order.signature_type as u8));
+
+    H256::from_slice(&keccak256(&order_data))
+}
+
+/// Parse a 256-bit integer from either a `0x`-prefixed hex string or a
+/// plain base-10 decimal string - Polymarket's CLOB API hands back token
+/// IDs in both forms depending on endpoint.
+fn parse_u256(input: &str) -> Result<U256, String> {
+    match input.strip_prefix("0x") {
+        Some(hex) => U256::from_str_radix(hex, 16).map_err(|e| e.to_string()),
+        None => U256::from_dec_str(input).map_err(|e| e.to_string()),
+    }
+}
+
+/// Keccak256 hash
 fn keccak256(data: &[u8]) -> [u8; 32] {
     let mut hasher = Keccak256::new();
     hasher.update(data);
     hasher.finalize().into()
 }
 
-/// EIP-712 Domain Separator
-pub fn domain_separator(verifying_contract: &str) -> [u8; 32] {
-    let type_hash = keccak256(
-        b"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)"
-    );
-    
-    let name_hash = keccak256(DOMAIN_NAME.as_bytes());
-    let version_hash = keccak256(DOMAIN_VERSION.as_bytes());
-    
-    // Encode domain struct
-    let mut encoded = Vec::new();
-    encoded.extend_from_slice(&type_hash);
-    encoded.extend_from_slice(&name_hash);
-    encoded.extend_from_slice(&version_hash);
-    encoded.extend_from_slice(&encode_u256(CHAIN_ID));
-    encoded.extend_from_slice(&encode_address(verifying_contract));
-    
-    keccak256(&encoded)
-}
-
-/// Encode u64 as u256 (32 bytes, big-endian, left-padded)
-fn encode_u256(value: u64) -> [u8; 32] {
-    let mut buf = [0u8; 32];
-    buf[24..32].copy_from_slice(&value.to_be_bytes());
-    buf
-}
-
-/// Encode address string to 32 bytes
-fn encode_address(addr: &str) -> [u8; 32] {
-    let addr = addr.strip_prefix("0x").unwrap_or(addr);
-    let mut buf = [0u8; 32];
-    if let Ok(bytes) = hex::decode(addr) {
-        let start = 32 - bytes.len().min(20);
-        buf[start..start + bytes.len().min(20)].copy_from_slice(&bytes[..bytes.len().min(20)]);
-    }
-    buf
-}
-
-/// Order type hash for EIP-712
-pub fn order_type_hash() -> [u8; 32] {
-    keccak256(
-        b"Order(uint256 salt,address maker,address signer,address taker,uint256 tokenId,uint256 makerAmount,uint256 taker,uint256 expiration,uint256 nonce,uint256 feeRateBps,uint8 side,uint8 signatureType)"
-    )
+/// Convert U256 to 32-byte array
+fn u256_to_bytes32(value: U256) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    bytes
+}
+
+/// Convert Address to 32-byte array (left-padded)
+fn address_to_bytes32(addr: Address) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes[12..].copy_from_slice(addr.as_bytes());
+    bytes
+}
+
+/// Convert u8 to 32-byte array (left-padded)
+fn u8_to_bytes32(value: u8) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes[31] = value;
+    bytes
+}
+
+/// CTF Exchange `nonces(address)` selector: the first 4 bytes of
+/// `keccak256("nonces(address)")`.
+fn nonces_selector() -> [u8; 4] {
+    let mut selector = [0u8; 4];
+    selector.copy_from_slice(&keccak256(b"nonces(address)")[..4]);
+    selector
+}
+
+/// CTF Exchange `incrementNonce()` selector.
+fn increment_nonce_selector() -> [u8; 4] {
+    let mut selector = [0u8; 4];
+    selector.copy_from_slice(&keccak256(b"incrementNonce()")[..4]);
+    selector
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SignerError {
+    #[error("Invalid private key: {0}")]
+    InvalidKey(String),
+
+    #[error("Invalid token ID: {0}")]
+    InvalidTokenId(String),
+
+    #[error("Signing error: {0}")]
+    SigningError(String),
+
+    #[error("Hardware signer error: {0}")]
+    Hardware(String),
+
+    #[error("Serialization error: {0}")]
+    Serialization(String),
+
+    #[error("no RPC provider attached to this signer; call with_provider first")]
+    NoProvider,
+
+    #[error("chain error: {0}")]
+    ChainError(String),
+
+    #[error("a proxy wallet requires signature_type Poly or PolyProxy, not Eoa")]
+    InvalidProxySignatureType,
+}
+
+fn parse_address(addr: &str) -> PyResult<Address> {
+    Address::from_str(addr)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("invalid address: {e}")))
 }
 
-/// Hash an order for signing
-pub fn hash_order(order: &Order) -> [u8; 32] {
-    let type_hash = order_type_hash();
-    
-    let mut encoded = Vec::new();
-    encoded.extend_from_slice(&type_hash);
-    encoded.extend_from_slice(&encode_u256(u64::from_str_radix(order.salt.trim_start_matches("0x"), 16).unwrap_or(0)));
-    encoded.extend_from_slice(&encode_address(&order.maker));
-    encoded.extend_from_slice(&encode_address(&order.signer));
-    encoded.extend_from_slice(&encode_address(&order.taker));
-    encoded.extend_from_slice(&encode_u256(order.token_id.parse().unwrap_or(0)));
-    encoded.extend_from_slice(&encode_u256(order.maker_amount.parse().unwrap_or(0)));
-    encoded.extend_from_slice(&encode_u256(order.taker_amount.parse().unwrap_or(0)));
-    encoded.extend_from_slice(&encode_u256(order.expiration.parse().unwrap_or(0)));
-    encoded.extend_from_slice(&encode_u256(order.nonce.parse().unwrap_or(0)));
-    encoded.extend_from_slice(&encode_u256(order.fee_rate_bps.parse().unwrap_or(0)));
-    encoded.extend_from_slice(&encode_u256(order.side as u64));
-    encoded.extend_from_slice(&encode_u256(order.signature_type as u64));
-    
-    keccak256(&encoded)
-}
-
-/// Create the final EIP-712 message hash
-pub fn eip712_hash(order: &Order, verifying_contract: &str) -> [u8; 32] {
-    let domain_sep = domain_separator(verifying_contract);
-    let order_hash = hash_order(order);
-    
-    let mut msg = Vec::with_capacity(66);
-    msg.push(0x19);
-    msg.push(0x01);
-    msg.extend_from_slice(&domain_sep);
-    msg.extend_from_slice(&order_hash);
-    
-    keccak256(&msg)
+fn build_provider(rpc_url: &str) -> PyResult<Provider<Http>> {
+    Provider::<Http>::try_from(rpc_url)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("invalid rpc_url: {e}")))
+}
+
+fn parse_signature_type(signature_type: &str) -> PyResult<SignatureType> {
+    match signature_type.to_uppercase().as_str() {
+        "EOA" => Ok(SignatureType::Eoa),
+        "POLY" => Ok(SignatureType::Poly),
+        "POLY_PROXY" | "POLYPROXY" => Ok(SignatureType::PolyProxy),
+        other => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "unknown signature_type: {other}"
+        ))),
+    }
 }
 
 // ============ PyO3 Bindings ============
 
 #[pyclass]
 pub struct PySigner {
-    wallet_address: String,
-    ctf_exchange: String,
+    inner: PolymarketSigner,
+    runtime: tokio::runtime::Runtime,
 }
 
 #[pymethods]
 impl PySigner {
+    /// `funder`/`signature_type` let the order be attributed to a
+    /// Polymarket proxy wallet (gasless trading) rather than this EOA
+    /// directly. `signature_type` is one of `"EOA"`, `"POLY"`,
+    /// `"POLY_PROXY"` and is ignored when `funder` is `None`; when `funder`
+    /// is given, `signature_type` must be overridden to `"POLY"` or
+    /// `"POLY_PROXY"` - the default `"EOA"` is rejected rather than silently
+    /// producing a proxy-attributed order signed as if it were an EOA's
+    /// own. `rpc_url`, if given, attaches a Polygon RPC provider so
+    /// `sync_nonce`/`cancel_all_on_chain` can talk to the CTF Exchange
+    /// contract directly.
     #[new]
-    #[pyo3(signature = (wallet_address, ctf_exchange="0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E"))]
-    fn new(wallet_address: &str, ctf_exchange: &str) -> Self {
-        Self {
-            wallet_address: wallet_address.to_string(),
-            ctf_exchange: ctf_exchange.to_string(),
+    #[pyo3(signature = (private_key, is_negrisk=false, funder=None, signature_type="EOA", rpc_url=None))]
+    pub fn new(
+        private_key: &str,
+        is_negrisk: bool,
+        funder: Option<&str>,
+        signature_type: &str,
+        rpc_url: Option<&str>,
+    ) -> PyResult<Self> {
+        let runtime = tokio::runtime::Runtime::new()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let mut inner = PolymarketSigner::new(private_key, is_negrisk)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+
+        if let Some(funder) = funder {
+            inner = inner
+                .with_proxy(parse_address(funder)?, parse_signature_type(signature_type)?)
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
         }
+
+        if let Some(rpc_url) = rpc_url {
+            inner = inner.with_provider(Arc::new(build_provider(rpc_url)?));
+        }
+
+        Ok(Self { inner, runtime })
+    }
+
+    /// Build a signer backed by a Ledger hardware wallet instead of an
+    /// in-memory key, so the private key never has to leave the device.
+    #[staticmethod]
+    #[pyo3(signature = (account_index=0, is_negrisk=false))]
+    pub fn with_ledger(account_index: usize, is_negrisk: bool) -> PyResult<Self> {
+        let runtime = tokio::runtime::Runtime::new()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let inner = runtime
+            .block_on(PolymarketSigner::with_ledger(account_index, is_negrisk))
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        Ok(Self { inner, runtime })
+    }
+
+    /// Build a signer whose key is re-derived from a mnemonic + account
+    /// index on every construction, so many sub-accounts can share one seed
+    /// phrase instead of a key file apiece.
+    #[staticmethod]
+    #[pyo3(signature = (phrase, account_index=0, is_negrisk=false))]
+    pub fn from_mnemonic(phrase: &str, account_index: u32, is_negrisk: bool) -> PyResult<Self> {
+        let runtime = tokio::runtime::Runtime::new()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let inner = PolymarketSigner::from_mnemonic(phrase, account_index, is_negrisk)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+
+        Ok(Self { inner, runtime })
     }
 
-    /// Create an order and return its hash for signing
-    fn create_order_hash(
+    /// Get signer address
+    pub fn address(&self) -> String {
+        format!("{:?}", self.inner.address())
+    }
+
+    /// Create and sign a BUY limit order, returns CLOB-ready JSON
+    pub fn create_buy_order(
         &self,
         token_id: &str,
-        side: &str, // "buy" or "sell"
-        price: f64,
-        size: f64,
+        price: &str,
+        size: &str,
         expiration_secs: u64,
     ) -> PyResult<String> {
-        let order_side = match side.to_lowercase().as_str() {
-            "buy" => OrderSide::Buy,
-            "sell" => OrderSide::Sell,
-            _ => return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid side")),
-        };
+        let price_dec = Decimal::from_str(price)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        let size_dec = Decimal::from_str(size)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
 
-        let order = Order::new(
-            self.wallet_address.clone(),
-            token_id.to_string(),
-            order_side,
-            price,
-            size,
-            expiration_secs,
-        );
+        self.runtime.block_on(async {
+            let signed = self
+                .inner
+                .create_limit_order(token_id, price_dec, size_dec, Side::Buy, expiration_secs)
+                .await
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
 
-        let hash = eip712_hash(&order, &self.ctf_exchange);
-        Ok(format!("0x{}", hex::encode(hash)))
+            signed
+                .to_clob_json()
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+        })
     }
 
-    /// Build a complete order as JSON
-    fn build_order(
+    /// Create and sign a SELL limit order, returns CLOB-ready JSON
+    pub fn create_sell_order(
         &self,
         token_id: &str,
-        side: &str,
-        price: f64,
-        size: f64,
+        price: &str,
+        size: &str,
         expiration_secs: u64,
     ) -> PyResult<String> {
-        let order_side = match side.to_lowercase().as_str() {
-            "buy" => OrderSide::Buy,
-            "sell" => OrderSide::Sell,
-            _ => return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid side")),
-        };
+        let price_dec = Decimal::from_str(price)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        let size_dec = Decimal::from_str(size)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
 
-        let order = Order::new(
-            self.wallet_address.clone(),
-            token_id.to_string(),
-            order_side,
-            price,
-            size,
-            expiration_secs,
-        );
+        self.runtime.block_on(async {
+            let signed = self
+                .inner
+                .create_limit_order(token_id, price_dec, size_dec, Side::Sell, expiration_secs)
+                .await
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            signed
+                .to_clob_json()
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+        })
+    }
+
+    /// Set the current nonce
+    pub fn set_nonce(&self, nonce: u64) {
+        self.inner.set_nonce(nonce);
+    }
 
-        serde_json::to_string(&order)
+    /// Cancel all orders by incrementing nonce
+    pub fn cancel_all(&self) {
+        self.inner.cancel_all_orders();
+    }
+
+    /// Read the maker's nonce from the CTF Exchange contract and adopt it
+    /// locally. Requires `rpc_url` to have been set at construction.
+    pub fn sync_nonce(&self) -> PyResult<u64> {
+        self.runtime
+            .block_on(self.inner.sync_nonce())
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
     }
 
-    /// Get domain separator hash
-    fn domain_separator(&self) -> String {
-        let sep = domain_separator(&self.ctf_exchange);
-        format!("0x{}", hex::encode(sep))
+    /// Submit the `incrementNonce` transaction on-chain, making cancellation
+    /// effective against the matching engine, then refresh the local nonce.
+    /// Requires `rpc_url` to have been set at construction.
+    pub fn cancel_all_on_chain(&self) -> PyResult<()> {
+        self.runtime
+            .block_on(self.inner.cancel_all_on_chain())
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
     }
 
+    /// Deliberately surfaces only the public address - private key material
+    /// (raw, mnemonic-derived, or hardware-backed) must never appear here.
     fn __repr__(&self) -> String {
-        format!("Signer(wallet={}, exchange={})", 
-            &self.wallet_address[..10.min(self.wallet_address.len())],
-            &self.ctf_exchange[..10.min(self.ctf_exchange.len())]
+        format!("PySigner(address={})", self.address())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn generate_salt_has_no_duplicates_back_to_back() {
+        let mut seen = HashSet::new();
+        for _ in 0..10_000 {
+            assert!(seen.insert(generate_salt()), "duplicate salt generated");
+        }
+    }
+
+    #[test]
+    fn parse_u256_accepts_hex_and_decimal_equivalently() {
+        let from_hex = parse_u256("0x2a").unwrap();
+        let from_decimal = parse_u256("42").unwrap();
+        assert_eq!(from_hex, from_decimal);
+        assert_eq!(from_hex, U256::from(42u64));
+    }
+
+    fn sample_order(salt: U256) -> Order {
+        Order {
+            salt,
+            maker: Address::from_low_u64_be(1),
+            signer: Address::from_low_u64_be(1),
+            taker: Address::zero(),
+            token_id: U256::from(12345u64),
+            maker_amount: U256::from(1_000_000u64),
+            taker_amount: U256::from(500_000u64),
+            expiration: U256::from(1_700_000_000u64),
+            nonce: U256::zero(),
+            fee_rate_bps: U256::zero(),
+            side: Side::Buy,
+            signature_type: SignatureType::Eoa,
+        }
+    }
+
+    #[test]
+    fn hash_order_is_deterministic() {
+        let order = sample_order(U256::from(7u64));
+        assert_eq!(hash_order(&order), hash_order(&order));
+    }
+
+    #[test]
+    fn hash_order_differs_when_salt_differs() {
+        let a = sample_order(U256::from(7u64));
+        let b = sample_order(U256::from(8u64));
+        assert_ne!(hash_order(&a), hash_order(&b));
+    }
+
+    fn sample_signer() -> PolymarketSigner {
+        PolymarketSigner::new(
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+            false,
         )
+        .unwrap()
+    }
+
+    #[test]
+    fn with_proxy_rejects_eoa_signature_type() {
+        let err = sample_signer()
+            .with_proxy(Address::from_low_u64_be(1), SignatureType::Eoa)
+            .unwrap_err();
+        assert!(matches!(err, SignerError::InvalidProxySignatureType));
+    }
+
+    #[test]
+    fn with_proxy_accepts_poly_signature_type() {
+        let signer = sample_signer()
+            .with_proxy(Address::from_low_u64_be(1), SignatureType::Poly)
+            .unwrap();
+        assert_eq!(signer.proxy.unwrap().signature_type, SignatureType::Poly);
     }
 }
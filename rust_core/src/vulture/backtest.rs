@@ -0,0 +1,274 @@
+//! Vulture Backtest Module
+//!
+//! Replays a time-ordered tick stream through `Vulture::scan`, simulating
+//! maker fills and rebate/fee accounting, so `VultureConfig` choices can be
+//! validated offline before risking capital.
+
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::{Vulture, VultureConfig};
+
+/// Maker rebate earned per filled quote on 15-min crypto markets (decimal)
+const MAKER_REBATE: f64 = 0.0; // Polymarket rebates are paid out-of-band; tracked here as realized edge
+/// Taker fee assumption if a quote crosses instead of resting (decimal)
+const TAKER_FEE: f64 = 0.02;
+
+/// One input tick: a snapshot of best bid/ask for a market at a point in time
+#[derive(Debug, Clone)]
+pub struct Tick {
+    pub timestamp: u64,
+    pub market_slug: String,
+    pub condition_id: String,
+    pub best_bid: f64,
+    pub best_ask: f64,
+}
+
+/// A single simulated fill recorded during the backtest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SimFill {
+    timestamp: u64,
+    rebate_pnl: f64,
+    won: bool,
+}
+
+/// Per-calendar-day rollup of backtest fills
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[pyclass]
+pub struct DayBreakdown {
+    #[pyo3(get)]
+    pub date: String,
+    #[pyo3(get)]
+    pub trades: u32,
+    #[pyo3(get)]
+    pub wins: u32,
+    #[pyo3(get)]
+    pub losses: u32,
+    #[pyo3(get)]
+    pub net_rebate_pnl: f64,
+}
+
+#[pymethods]
+impl DayBreakdown {
+    fn __repr__(&self) -> String {
+        format!(
+            "DayBreakdown({}: trades={}, wins={}, losses={}, net_pnl={:.4})",
+            self.date, self.trades, self.wins, self.losses, self.net_rebate_pnl
+        )
+    }
+}
+
+/// Result of replaying a tick stream through the Vulture strategy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[pyclass]
+pub struct BacktestResult {
+    #[pyo3(get)]
+    pub total_rebate_pnl: f64,
+    #[pyo3(get)]
+    pub fill_count: u32,
+    #[pyo3(get)]
+    pub win_rate: f64,
+    fills: Vec<SimFill>,
+}
+
+#[pymethods]
+impl BacktestResult {
+    /// Per-calendar-day rows of (date, trades, wins, losses, net rebate PnL)
+    fn daily_breakdown(&self) -> Vec<DayBreakdown> {
+        let mut by_day: BTreeMap<String, (u32, u32, u32, f64)> = BTreeMap::new();
+
+        for fill in &self.fills {
+            let date = unix_day(fill.timestamp);
+            let entry = by_day.entry(date).or_insert((0, 0, 0, 0.0));
+            entry.0 += 1;
+            if fill.won {
+                entry.1 += 1;
+            } else {
+                entry.2 += 1;
+            }
+            entry.3 += fill.rebate_pnl;
+        }
+
+        by_day
+            .into_iter()
+            .map(|(date, (trades, wins, losses, net_rebate_pnl))| DayBreakdown {
+                date,
+                trades,
+                wins,
+                losses,
+                net_rebate_pnl,
+            })
+            .collect()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "BacktestResult(fills={}, win_rate={:.1}%, net_pnl={:.4})",
+            self.fill_count,
+            self.win_rate * 100.0,
+            self.total_rebate_pnl
+        )
+    }
+}
+
+/// Naive unix-day bucketing (no timezone adjustment) into a "YYYY-MM-DD" key.
+/// Good enough for grouping backtest ticks; not a general calendar utility.
+fn unix_day(ts: u64) -> String {
+    let days_since_epoch = ts / 86_400;
+    // 1970-01-01 is day 0; walk forward using the civil_from_days algorithm (Howard Hinnant).
+    let z = days_since_epoch as i64 + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = (z - era * 146_097) as u64;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let y = yoe as i64 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = if m <= 2 { y + 1 } else { y };
+    format!("{:04}-{:02}-{:02}", y, m, d)
+}
+
+/// Replay `ticks` through `vulture.scan`, simulating a maker fill whenever
+/// the *opposing* side of the market crosses the recommended quote price.
+pub fn run_backtest(vulture: &Vulture, ticks: &[Tick]) -> BacktestResult {
+    let mut fills = Vec::new();
+
+    for window in ticks.windows(2) {
+        let (now, next) = (&window[0], &window[1]);
+        let Some(opp) = vulture.scan(&now.market_slug, &now.condition_id, now.best_bid, now.best_ask) else {
+            continue;
+        };
+
+        // A resting quote fills when the *next* tick's opposing side crosses it.
+        let crossed = match opp.recommended_side.as_str() {
+            "BUY" => next.best_ask <= opp.recommended_price,
+            _ => next.best_bid >= opp.recommended_price,
+        };
+
+        if !crossed {
+            continue;
+        }
+
+        // Signed edge relative to the post-fill fair value (next tick's mid),
+        // not a `.abs()`-ed touch distance: a BUY filled above fair value or
+        // a SELL filled below it is adverse selection and must show up as a
+        // loss, not get folded into `win_rate`/`daily_breakdown` as a win.
+        let next_mid = (next.best_bid + next.best_ask) / 2.0;
+        let edge = match opp.recommended_side.as_str() {
+            "BUY" => next_mid - opp.recommended_price,
+            _ => opp.recommended_price - next_mid,
+        };
+        let rebate_pnl = if opp.use_post_only { edge + MAKER_REBATE } else { edge - TAKER_FEE };
+
+        fills.push(SimFill {
+            timestamp: next.timestamp,
+            rebate_pnl,
+            won: rebate_pnl > 0.0,
+        });
+    }
+
+    let fill_count = fills.len() as u32;
+    let wins = fills.iter().filter(|f| f.won).count() as u32;
+    let total_rebate_pnl = fills.iter().map(|f| f.rebate_pnl).sum();
+    let win_rate = if fill_count > 0 { wins as f64 / fill_count as f64 } else { 0.0 };
+
+    BacktestResult {
+        total_rebate_pnl,
+        fill_count,
+        win_rate,
+        fills,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vulture::Vulture;
+
+    fn tick(timestamp: u64, best_bid: f64, best_ask: f64) -> Tick {
+        Tick {
+            timestamp,
+            market_slug: "btc-15m".to_string(),
+            condition_id: "cond1".to_string(),
+            best_bid,
+            best_ask,
+        }
+    }
+
+    #[test]
+    fn no_fill_when_the_opposing_side_never_crosses() {
+        let vulture = Vulture::new();
+        let ticks = vec![tick(0, 0.495, 0.505), tick(60, 0.494, 0.506)];
+        let result = run_backtest(&vulture, &ticks);
+        assert_eq!(result.fill_count, 0);
+    }
+
+    #[test]
+    fn a_crossed_fill_records_a_negative_edge_as_adverse_selection() {
+        // The resting BUY rests at best_bid + 0.25*spread = 0.4975; the next
+        // tick's ask dropping to 0.495 crosses it (market ran down through
+        // our bid), so fair value afterward is below what we paid.
+        let vulture = Vulture::new();
+        let ticks = vec![tick(0, 0.495, 0.505), tick(60, 0.485, 0.495)];
+        let result = run_backtest(&vulture, &ticks);
+
+        assert_eq!(result.fill_count, 1);
+        assert!(result.total_rebate_pnl < 0.0);
+        assert_eq!(result.win_rate, 0.0);
+    }
+
+    #[test]
+    fn daily_breakdown_groups_fills_by_calendar_day() {
+        let vulture = Vulture::new();
+        let ticks = vec![
+            tick(1_704_067_200, 0.495, 0.505), // 2024-01-01
+            tick(1_704_067_260, 0.485, 0.495),
+            tick(1_710_460_800, 0.495, 0.505), // 2024-03-15
+            tick(1_710_460_860, 0.485, 0.495),
+        ];
+        let result = run_backtest(&vulture, &ticks);
+        let days = result.daily_breakdown();
+
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].date, "2024-01-01");
+        assert_eq!(days[1].date, "2024-03-15");
+    }
+
+    #[test]
+    fn unix_day_matches_known_calendar_dates() {
+        assert_eq!(unix_day(1_704_067_200), "2024-01-01");
+        assert_eq!(unix_day(1_710_460_800), "2024-03-15");
+    }
+}
+
+// ============ PyO3 Bindings ============
+
+#[pyclass]
+pub struct PyBacktester {
+    vulture: Vulture,
+}
+
+#[pymethods]
+impl PyBacktester {
+    #[new]
+    fn new(config: VultureConfig) -> Self {
+        Self { vulture: Vulture::with_config(config) }
+    }
+
+    /// Run the backtest over a list of (timestamp, market_slug, condition_id, best_bid, best_ask)
+    fn run(&self, ticks: Vec<(u64, String, String, f64, f64)>) -> BacktestResult {
+        let ticks: Vec<Tick> = ticks
+            .into_iter()
+            .map(|(timestamp, market_slug, condition_id, best_bid, best_ask)| Tick {
+                timestamp,
+                market_slug,
+                condition_id,
+                best_bid,
+                best_ask,
+            })
+            .collect();
+        run_backtest(&self.vulture, &ticks)
+    }
+}
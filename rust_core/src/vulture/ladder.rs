@@ -0,0 +1,206 @@
+//! Multi-level POST_ONLY quote ladder for the zombie market maker.
+//!
+//! `Vulture::scan` only ever returns a single recommended price per side,
+//! which leaves rebate/fill capture thin on a wide 15-minute spread. This
+//! replicates a linear liquidity curve across the book instead: `levels`
+//! evenly-spaced quotes per side, spanning from just inside the touch
+//! toward mid, sized by a configurable profile. Every level stays strictly
+//! between the touch and mid so none can ever cross and lose the maker
+//! rebate.
+
+use std::collections::HashSet;
+
+use pyo3::prelude::*;
+
+/// Per-level size profile across the ladder
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeProfile {
+    /// Every level gets an equal share of the budget
+    Flat,
+    /// Levels closest to the touch get the largest share
+    FrontLoaded,
+    /// Levels closest to mid get the largest share
+    BackLoaded,
+}
+
+impl SizeProfile {
+    pub fn from_str_pub(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "flat" => Some(SizeProfile::Flat),
+            "front_loaded" | "front-loaded" => Some(SizeProfile::FrontLoaded),
+            "back_loaded" | "back-loaded" => Some(SizeProfile::BackLoaded),
+            _ => None,
+        }
+    }
+
+    /// Per-level weight for `levels` total levels, index 0 = closest to the touch
+    fn weights(self, levels: usize) -> Vec<f64> {
+        match self {
+            SizeProfile::Flat => vec![1.0; levels],
+            SizeProfile::FrontLoaded => (0..levels).map(|i| (levels - i) as f64).collect(),
+            SizeProfile::BackLoaded => (0..levels).map(|i| (i + 1) as f64).collect(),
+        }
+    }
+}
+
+/// One quote in the ladder
+#[derive(Debug, Clone)]
+#[pyclass]
+pub struct QuoteLevel {
+    #[pyo3(get)]
+    pub side: String,
+    #[pyo3(get)]
+    pub price: f64,
+    #[pyo3(get)]
+    pub size: f64,
+    #[pyo3(get)]
+    pub post_only: bool,
+}
+
+#[pymethods]
+impl QuoteLevel {
+    fn __repr__(&self) -> String {
+        format!("QuoteLevel({} {:.4} x {:.2}, post_only={})", self.side, self.price, self.size, self.post_only)
+    }
+}
+
+/// Build a symmetric `levels`-deep ladder per side, sized per `profile`,
+/// spanning from just inside the touch toward mid. Returns both sides
+/// concatenated (bids first, then asks); either side is empty if there
+/// isn't room between the touch and mid for even one tick.
+pub fn build_ladder(
+    best_bid: f64,
+    best_ask: f64,
+    levels: usize,
+    total_size: f64,
+    profile: SizeProfile,
+    post_only: bool,
+    tick_size: f64,
+) -> Vec<QuoteLevel> {
+    if levels == 0 || total_size <= 0.0 || tick_size <= 0.0 || best_bid <= 0.0 || best_ask <= best_bid {
+        return Vec::new();
+    }
+    let mid = (best_bid + best_ask) / 2.0;
+    let weights = profile.weights(levels);
+    let weight_sum: f64 = weights.iter().sum();
+
+    let mut ladder = build_side(best_bid + tick_size, mid, true, levels, "bid", &weights, weight_sum, total_size, post_only, tick_size);
+    ladder.extend(build_side(best_ask - tick_size, mid, false, levels, "ask", &weights, weight_sum, total_size, post_only, tick_size));
+    ladder
+}
+
+#[allow(clippy::too_many_arguments)]
+fn build_side(
+    near_touch: f64,
+    mid: f64,
+    is_bid: bool,
+    levels: usize,
+    side: &str,
+    weights: &[f64],
+    weight_sum: f64,
+    total_size: f64,
+    post_only: bool,
+    tick_size: f64,
+) -> Vec<QuoteLevel> {
+    let has_room = if is_bid { near_touch > 0.0 && near_touch < mid } else { near_touch > mid };
+    if !has_room {
+        return Vec::new();
+    }
+
+    // Space `levels` quotes over `levels + 1` steps (not `levels - 1`) so the
+    // innermost level lands strictly short of mid instead of exactly on it -
+    // `levels - 1` steps puts the last quote at `near_touch + span`, i.e.
+    // mid itself, which the `inside` check below then silently drops.
+    let span = mid - near_touch;
+    let step = span / (levels + 1) as f64;
+
+    let mut seen_ticks = HashSet::new();
+    let mut out = Vec::with_capacity(levels);
+    for (i, &weight) in weights.iter().enumerate().take(levels) {
+        let raw_price = near_touch + step * i as f64;
+        let price = (raw_price / tick_size).round() * tick_size;
+
+        // Strictly inside the spread: never at or past mid.
+        let inside = if is_bid { price > 0.0 && price < mid } else { price > mid };
+        if !inside {
+            continue;
+        }
+
+        let tick = (price / tick_size).round() as i64;
+        if !seen_ticks.insert(tick) {
+            continue; // already-occupied tick: skip rather than double-quote it
+        }
+
+        out.push(QuoteLevel {
+            side: side.to_string(),
+            price,
+            size: total_size * weight / weight_sum,
+            post_only,
+        });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flat_profile_splits_size_evenly_across_levels() {
+        let ladder = build_ladder(0.40, 0.60, 4, 100.0, SizeProfile::Flat, true, 0.001);
+        let bids: Vec<&QuoteLevel> = ladder.iter().filter(|l| l.side == "bid").collect();
+        assert_eq!(bids.len(), 4);
+        for level in &bids {
+            assert!((level.size - 25.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn front_loaded_profile_weights_levels_closest_to_touch_most() {
+        let ladder = build_ladder(0.40, 0.60, 3, 60.0, SizeProfile::FrontLoaded, true, 0.001);
+        let bids: Vec<&QuoteLevel> = ladder.iter().filter(|l| l.side == "bid").collect();
+        assert!(bids[0].size > bids[1].size);
+        assert!(bids[1].size > bids[2].size);
+    }
+
+    #[test]
+    fn back_loaded_profile_weights_levels_closest_to_mid_most() {
+        let ladder = build_ladder(0.40, 0.60, 3, 60.0, SizeProfile::BackLoaded, true, 0.001);
+        let bids: Vec<&QuoteLevel> = ladder.iter().filter(|l| l.side == "bid").collect();
+        assert!(bids[0].size < bids[1].size);
+        assert!(bids[1].size < bids[2].size);
+    }
+
+    #[test]
+    fn every_level_stays_strictly_between_touch_and_mid() {
+        let ladder = build_ladder(0.40, 0.60, 5, 100.0, SizeProfile::Flat, true, 0.001);
+        let mid = 0.5;
+        for level in &ladder {
+            if level.side == "bid" {
+                assert!(level.price > 0.40 && level.price < mid);
+            } else {
+                assert!(level.price < 0.60 && level.price > mid);
+            }
+        }
+    }
+
+    #[test]
+    fn no_room_between_touch_and_mid_yields_empty_side() {
+        // Touch is one tick wide, so best_bid + tick_size lands exactly at mid - no room.
+        let ladder = build_ladder(0.499, 0.501, 3, 30.0, SizeProfile::Flat, true, 0.001);
+        assert!(ladder.iter().all(|l| l.side != "bid"));
+    }
+
+    #[test]
+    fn zero_levels_or_size_yields_an_empty_ladder() {
+        assert!(build_ladder(0.40, 0.60, 0, 100.0, SizeProfile::Flat, true, 0.001).is_empty());
+        assert!(build_ladder(0.40, 0.60, 3, 0.0, SizeProfile::Flat, true, 0.001).is_empty());
+    }
+
+    #[test]
+    fn size_profile_from_str_pub_accepts_both_separators() {
+        assert_eq!(SizeProfile::from_str_pub("front_loaded"), Some(SizeProfile::FrontLoaded));
+        assert_eq!(SizeProfile::from_str_pub("front-loaded"), Some(SizeProfile::FrontLoaded));
+        assert_eq!(SizeProfile::from_str_pub("bogus"), None);
+    }
+}
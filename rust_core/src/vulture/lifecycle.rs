@@ -0,0 +1,190 @@
+//! Order lifecycle management: time-in-force semantics and unfilled-quote requoting.
+//!
+//! The Vulture only ever emits a price; nothing tracks what happens to the
+//! resting order after that. This module attaches a time-in-force to each
+//! quote and, mirroring freqtrade's `unfilledtimeout`, decides whether a
+//! stale resting order should be cancelled-and-requoted or abandoned.
+
+use pyo3::prelude::*;
+
+use super::{MarketOpportunity, Vulture};
+
+/// Time-in-force mode for a resting quote
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeInForce {
+    /// Good-til-cancelled: rests until explicitly cancelled
+    Gtc,
+    /// Immediate-or-cancel: fill what's available now, cancel the rest
+    Ioc,
+    /// Fill-or-kill: fill completely now or cancel entirely
+    Fok,
+    /// Good-til-date: rests until a fixed expiry timestamp
+    Gtd,
+}
+
+impl TimeInForce {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TimeInForce::Gtc => "GTC",
+            TimeInForce::Ioc => "IOC",
+            TimeInForce::Fok => "FOK",
+            TimeInForce::Gtd => "GTD",
+        }
+    }
+
+    pub fn from_str_pub(s: &str) -> Option<Self> {
+        match s.to_uppercase().as_str() {
+            "GTC" => Some(TimeInForce::Gtc),
+            "IOC" => Some(TimeInForce::Ioc),
+            "FOK" => Some(TimeInForce::Fok),
+            "GTD" => Some(TimeInForce::Gtd),
+            _ => None,
+        }
+    }
+}
+
+/// A quote the bot believes is resting on the book, tracked for timeout/requote purposes
+#[derive(Debug, Clone)]
+#[pyclass]
+pub struct RestingOrder {
+    #[pyo3(get)]
+    pub market_slug: String,
+    #[pyo3(get)]
+    pub condition_id: String,
+    #[pyo3(get)]
+    pub side: String,
+    #[pyo3(get)]
+    pub price: f64,
+    #[pyo3(get)]
+    pub time_in_force: String,
+    #[pyo3(get)]
+    pub placed_at_ts: u64,
+    #[pyo3(get)]
+    pub timeouts_used: u32,
+}
+
+#[pymethods]
+impl RestingOrder {
+    #[new]
+    fn new(market_slug: String, condition_id: String, side: String, price: f64, time_in_force: String, placed_at_ts: u64) -> Self {
+        Self { market_slug, condition_id, side, price, time_in_force, placed_at_ts, timeouts_used: 0 }
+    }
+}
+
+/// What to do about a resting order that hasn't filled
+#[derive(Debug, Clone)]
+#[pyclass]
+pub struct UnfilledDecision {
+    /// "WAIT" | "REQUOTE" | "GIVE_UP"
+    #[pyo3(get)]
+    pub action: String,
+    #[pyo3(get)]
+    pub new_quote: Option<MarketOpportunity>,
+    #[pyo3(get)]
+    pub timeouts_used: u32,
+}
+
+#[pymethods]
+impl UnfilledDecision {
+    fn __repr__(&self) -> String {
+        format!("UnfilledDecision(action='{}', timeouts_used={})", self.action, self.timeouts_used)
+    }
+}
+
+/// Decide what to do with a resting order that hasn't filled, given the current book.
+///
+/// GTD orders past their expiry (`placed_at_ts + gtd_seconds`) give up immediately.
+/// Otherwise, once `unfilled_timeout` has elapsed: requote from the fresh book if
+/// `exit_timeout_count` hasn't been exhausted, else give up.
+pub fn on_unfilled(
+    vulture: &Vulture,
+    order: &RestingOrder,
+    now_ts: u64,
+    best_bid: f64,
+    best_ask: f64,
+) -> UnfilledDecision {
+    let config = vulture.get_config();
+    let elapsed = now_ts.saturating_sub(order.placed_at_ts);
+
+    let gtd_expired = TimeInForce::from_str_pub(&order.time_in_force) == Some(TimeInForce::Gtd)
+        && elapsed >= config.gtd_seconds;
+
+    if gtd_expired {
+        return UnfilledDecision { action: "GIVE_UP".to_string(), new_quote: None, timeouts_used: order.timeouts_used };
+    }
+
+    if elapsed < config.unfilled_timeout {
+        return UnfilledDecision { action: "WAIT".to_string(), new_quote: None, timeouts_used: order.timeouts_used };
+    }
+
+    let timeouts_used = order.timeouts_used + 1;
+    if timeouts_used >= config.exit_timeout_count {
+        return UnfilledDecision { action: "GIVE_UP".to_string(), new_quote: None, timeouts_used };
+    }
+
+    let fresh_quote = vulture.scan(&order.market_slug, &order.condition_id, best_bid, best_ask);
+    UnfilledDecision { action: "REQUOTE".to_string(), new_quote: fresh_quote, timeouts_used }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vulture::VultureConfig;
+
+    fn order(time_in_force: &str, placed_at_ts: u64, timeouts_used: u32) -> RestingOrder {
+        let mut order = RestingOrder::new(
+            "btc-15m".to_string(),
+            "cond1".to_string(),
+            "BUY".to_string(),
+            0.495,
+            time_in_force.to_string(),
+            placed_at_ts,
+        );
+        order.timeouts_used = timeouts_used;
+        order
+    }
+
+    #[test]
+    fn waits_before_the_unfilled_timeout_elapses() {
+        let vulture = Vulture::new();
+        let decision = on_unfilled(&vulture, &order("GTC", 0, 0), 5, 0.495, 0.505);
+        assert_eq!(decision.action, "WAIT");
+    }
+
+    #[test]
+    fn requotes_once_unfilled_timeout_elapses_with_budget_remaining() {
+        let vulture = Vulture::new();
+        let decision = on_unfilled(&vulture, &order("GTC", 0, 0), 20, 0.495, 0.505);
+        assert_eq!(decision.action, "REQUOTE");
+        assert_eq!(decision.timeouts_used, 1);
+        assert!(decision.new_quote.is_some());
+    }
+
+    #[test]
+    fn gives_up_once_exit_timeout_count_is_exhausted() {
+        let mut config = VultureConfig::new();
+        config.exit_timeout_count = 2;
+        let vulture = Vulture::with_config(config);
+        let decision = on_unfilled(&vulture, &order("GTC", 0, 1), 20, 0.495, 0.505);
+        assert_eq!(decision.action, "GIVE_UP");
+    }
+
+    #[test]
+    fn gtd_order_gives_up_immediately_once_expired_even_before_unfilled_timeout() {
+        let mut config = VultureConfig::new();
+        config.gtd_seconds = 10;
+        config.unfilled_timeout = 9999;
+        let vulture = Vulture::with_config(config);
+        let decision = on_unfilled(&vulture, &order("GTD", 0, 0), 10, 0.495, 0.505);
+        assert_eq!(decision.action, "GIVE_UP");
+    }
+
+    #[test]
+    fn gtd_order_waits_before_expiry() {
+        let mut config = VultureConfig::new();
+        config.gtd_seconds = 100;
+        let vulture = Vulture::with_config(config);
+        let decision = on_unfilled(&vulture, &order("GTD", 0, 0), 5, 0.495, 0.505);
+        assert_eq!(decision.action, "WAIT");
+    }
+}
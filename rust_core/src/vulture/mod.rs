@@ -0,0 +1,439 @@
+//! Vulture Module - Bot 4: Zombie Market Maker
+//! 15-minute crypto POST_ONLY rebate farming
+
+pub mod backtest;
+pub mod ladder;
+pub mod lifecycle;
+pub mod optimize;
+
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[pyclass]
+pub struct MarketOpportunity {
+    #[pyo3(get)]
+    pub market_slug: String,
+    #[pyo3(get)]
+    pub condition_id: String,
+    #[pyo3(get)]
+    pub spread_bps: f64,
+    #[pyo3(get)]
+    pub best_bid: f64,
+    #[pyo3(get)]
+    pub best_ask: f64,
+    #[pyo3(get)]
+    pub mid_price: f64,
+    #[pyo3(get)]
+    pub is_crypto_15min: bool,
+    #[pyo3(get)]
+    pub recommended_side: String,
+    #[pyo3(get)]
+    pub recommended_price: f64,
+    #[pyo3(get)]
+    pub use_post_only: bool,
+    /// Net signed inventory for this condition_id at scan time
+    #[pyo3(get)]
+    pub inventory: f64,
+    /// Inventory skew applied to the two-sided quote, in bps of mid
+    #[pyo3(get)]
+    pub skew_bps: f64,
+    /// Inventory-skewed two-sided quote: the bid to rest
+    #[pyo3(get)]
+    pub quote_bid_price: f64,
+    /// Inventory-skewed two-sided quote: the ask to rest
+    #[pyo3(get)]
+    pub quote_ask_price: f64,
+    /// Time-in-force for the recommended quote: GTC, IOC, FOK, or GTD
+    #[pyo3(get)]
+    pub time_in_force: String,
+}
+
+#[pymethods]
+impl MarketOpportunity {
+    fn __repr__(&self) -> String {
+        format!("MarketOpportunity(slug='{}', spread={}bps, post_only={})",
+            self.market_slug, self.spread_bps, self.use_post_only)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[pyclass]
+pub struct VultureConfig {
+    #[pyo3(get, set)]
+    pub min_spread_bps: f64,
+    #[pyo3(get, set)]
+    pub max_spread_bps: f64,
+    #[pyo3(get, set)]
+    pub min_mid_price: f64,
+    #[pyo3(get, set)]
+    pub edge_fraction: f64,
+    #[pyo3(get, set)]
+    pub force_post_only: bool,
+    /// Fixed width of the two-sided quote before skew, in bps of mid (each
+    /// side sits half of it away from mid)
+    #[pyo3(get, set)]
+    pub base_spread_bps: f64,
+    /// Maximum inventory-driven skew applied to the two-sided quote, in bps of mid
+    #[pyo3(get, set)]
+    pub max_skew_bps: f64,
+    /// Inventory size (in condition_id units) at which skew saturates at max_skew_bps
+    #[pyo3(get, set)]
+    pub max_position: f64,
+    /// Rolling window (seconds) used to compute per-market realized performance
+    #[pyo3(get, set)]
+    pub performance_window_secs: u64,
+    /// Markets whose rolling PnL falls below this are dropped in rank_and_filter mode
+    #[pyo3(get, set)]
+    pub min_rolling_pnl: f64,
+    /// Time-in-force applied to recommended quotes: GTC, IOC, FOK, or GTD
+    #[pyo3(get, set)]
+    pub time_in_force: String,
+    /// Expiry window (seconds) for GTD quotes
+    #[pyo3(get, set)]
+    pub gtd_seconds: u64,
+    /// Seconds to wait for a resting quote to fill before treating it as stale
+    #[pyo3(get, set)]
+    pub unfilled_timeout: u64,
+    /// Number of unfilled timeouts after which a market is given up on: the
+    /// timeout that reaches this count gives up instead of requoting
+    #[pyo3(get, set)]
+    pub exit_timeout_count: u32,
+    /// Market tick size: `best_bid`/`best_ask` are quantized to this before
+    /// the spread-bps gate is tested, so a price a fraction of a tick off
+    /// can't flip `scan`'s accept/reject decision
+    #[pyo3(get, set)]
+    pub tick_size: f64,
+    /// Market lot size (minimum size increment), carried alongside `tick_size`
+    #[pyo3(get, set)]
+    pub lot_size: f64,
+}
+
+#[pymethods]
+impl VultureConfig {
+    #[new]
+    fn new() -> Self {
+        Self {
+            min_spread_bps: 50.0,
+            max_spread_bps: 500.0,
+            min_mid_price: 0.05,
+            edge_fraction: 0.25,
+            force_post_only: false,
+            base_spread_bps: 20.0,
+            max_skew_bps: 100.0,
+            max_position: 1000.0,
+            performance_window_secs: 3600,
+            min_rolling_pnl: 0.0,
+            time_in_force: "GTC".to_string(),
+            gtd_seconds: 30,
+            unfilled_timeout: 15,
+            exit_timeout_count: 3,
+            tick_size: 0.001,
+            lot_size: 1.0,
+        }
+    }
+}
+
+#[derive(Debug)]
+#[pyclass]
+pub struct Vulture {
+    config: VultureConfig,
+    /// Net signed exposure per condition_id (positive = long, negative = short)
+    inventory: RwLock<HashMap<String, f64>>,
+    /// Realized maker PnL ledger per condition_id: (timestamp, pnl) pairs, oldest first
+    performance_ledger: RwLock<HashMap<String, Vec<(u64, f64)>>>,
+}
+
+#[pymethods]
+impl Vulture {
+    #[new]
+    fn new() -> Self {
+        Self {
+            config: VultureConfig::new(),
+            inventory: RwLock::new(HashMap::new()),
+            performance_ledger: RwLock::new(HashMap::new()),
+        }
+    }
+
+    #[staticmethod]
+    fn with_config(config: VultureConfig) -> Self {
+        Self {
+            config,
+            inventory: RwLock::new(HashMap::new()),
+            performance_ledger: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Record a realized maker fill's PnL for a condition_id at the given timestamp
+    fn record_fill_pnl(&self, condition_id: &str, timestamp: u64, pnl: f64) {
+        let mut ledger = self.performance_ledger.write().unwrap();
+        ledger.entry(condition_id.to_string()).or_default().push((timestamp, pnl));
+    }
+
+    /// Rolling (summed pnl, trade count) for a condition_id within `performance_window_secs` of `now_ts`
+    fn rolling_performance(&self, condition_id: &str, now_ts: u64) -> (f64, u32) {
+        let window_start = now_ts.saturating_sub(self.config.performance_window_secs);
+        let ledger = self.performance_ledger.read().unwrap();
+        let Some(trades) = ledger.get(condition_id) else {
+            return (0.0, 0);
+        };
+        let recent: Vec<f64> = trades
+            .iter()
+            .filter(|(ts, _)| *ts >= window_start)
+            .map(|(_, pnl)| *pnl)
+            .collect();
+        (recent.iter().sum(), recent.len() as u32)
+    }
+
+    /// Adjust net signed inventory for a condition_id (e.g. +size on a BUY fill, -size on a SELL fill)
+    fn update_inventory(&self, condition_id: &str, delta: f64) {
+        let mut inventory = self.inventory.write().unwrap();
+        *inventory.entry(condition_id.to_string()).or_insert(0.0) += delta;
+    }
+
+    /// Current net signed inventory for a condition_id
+    fn get_inventory(&self, condition_id: &str) -> f64 {
+        self.inventory.read().unwrap().get(condition_id).copied().unwrap_or(0.0)
+    }
+
+    #[pyo3(name = "is_15min_crypto")]
+    fn is_15min_crypto(&self, market_slug: &str) -> bool {
+        let slug = market_slug.to_lowercase();
+        let cryptos = ["btc", "bitcoin", "eth", "ethereum", "sol", "solana", "xrp", "doge", "bnb", "ada", "avax", "matic", "link"];
+        let is_15min = slug.contains("15m") || slug.contains("15-min") || slug.contains("15min");
+        is_15min && cryptos.iter().any(|c| slug.contains(c))
+    }
+
+    #[pyo3(name = "scan")]
+    fn scan(&self, market_slug: &str, condition_id: &str, best_bid: f64, best_ask: f64) -> Option<MarketOpportunity> {
+        // Quantize the touch to whole ticks before gating on it, so a quote
+        // a fraction of a tick off the market's real grid can't nondeterministically
+        // flip the spread-bps accept/reject decision.
+        let tick = self.config.tick_size.max(1e-12);
+        let bid_ticks = (best_bid / tick).round() as i64;
+        let ask_ticks = (best_ask / tick).round() as i64;
+        if bid_ticks <= 0 || ask_ticks <= 0 || bid_ticks >= ask_ticks { return None; }
+
+        let mid_price = (best_bid + best_ask) / 2.0;
+        if mid_price < self.config.min_mid_price { return None; }
+
+        // Cross-multiply instead of dividing first, so the bps gate is an
+        // exact integer-tick comparison rather than testing a ratio that's
+        // already accumulated float rounding from the division.
+        let spread_ticks = (ask_ticks - bid_ticks) as f64;
+        let mid_ticks = (bid_ticks + ask_ticks) as f64 / 2.0;
+        let spread_bps_times_mid = spread_ticks * 10_000.0;
+        if spread_bps_times_mid < self.config.min_spread_bps * mid_ticks
+            || spread_bps_times_mid > self.config.max_spread_bps * mid_ticks
+        {
+            return None;
+        }
+
+        let spread = best_ask - best_bid;
+        let spread_bps = (spread / mid_price) * 10_000.0;
+        let is_crypto = self.is_15min_crypto(market_slug);
+        let use_post_only = is_crypto || self.config.force_post_only;
+        let edge = spread * self.config.edge_fraction;
+        let recommended_price = best_bid + edge;
+        let recommended_side = if recommended_price < mid_price { "BUY" } else { "SELL" };
+
+        let inventory = self.get_inventory(condition_id);
+        let skew_frac = (inventory / self.config.max_position).clamp(-1.0, 1.0);
+        let skew_bps = skew_frac * self.config.max_skew_bps;
+        // Long inventory (skew_bps > 0) shifts both sides down to favor getting lifted on the ask.
+        let skew_amount = mid_price * (skew_bps / 10_000.0);
+        let half_spread = mid_price * (self.config.base_spread_bps / 10_000.0) / 2.0;
+        let quote_bid_price = mid_price - half_spread - skew_amount;
+        let quote_ask_price = mid_price + half_spread - skew_amount;
+
+        Some(MarketOpportunity {
+            market_slug: market_slug.to_string(),
+            condition_id: condition_id.to_string(),
+            spread_bps, best_bid, best_ask, mid_price,
+            is_crypto_15min: is_crypto,
+            recommended_side: recommended_side.to_string(),
+            recommended_price, use_post_only,
+            inventory, skew_bps, quote_bid_price, quote_ask_price,
+            time_in_force: self.config.time_in_force.clone(),
+        })
+    }
+
+    /// Scan a batch of markets. When `rank_and_filter` is set, surviving opportunities are
+    /// sorted by rolling realized PnL (trade count as tie-breaker) and markets whose rolling
+    /// PnL is below `min_rolling_pnl` are dropped, concentrating capital on markets the
+    /// zombie-MM strategy is demonstrably working on rather than re-quoting chronic losers.
+    #[pyo3(name = "scan_batch", signature = (markets, rank_and_filter=false, now_ts=0))]
+    fn scan_batch(&self, markets: Vec<(String, String, f64, f64)>, rank_and_filter: bool, now_ts: u64) -> Vec<MarketOpportunity> {
+        let mut opportunities: Vec<MarketOpportunity> = markets
+            .into_iter()
+            .filter_map(|(slug, cid, bid, ask)| self.scan(&slug, &cid, bid, ask))
+            .collect();
+
+        if !rank_and_filter {
+            return opportunities;
+        }
+
+        let mut ranked: Vec<(MarketOpportunity, f64, u32)> = opportunities
+            .drain(..)
+            .map(|opp| {
+                let (pnl, trades) = self.rolling_performance(&opp.condition_id, now_ts);
+                (opp, pnl, trades)
+            })
+            .filter(|(_, pnl, _)| *pnl >= self.config.min_rolling_pnl)
+            .collect();
+
+        ranked.sort_by(|(_, pnl_a, trades_a), (_, pnl_b, trades_b)| {
+            pnl_b.partial_cmp(pnl_a).unwrap_or(std::cmp::Ordering::Equal).then(trades_b.cmp(trades_a))
+        });
+
+        ranked.into_iter().map(|(opp, _, _)| opp).collect()
+    }
+
+    /// Current config
+    fn get_config(&self) -> VultureConfig {
+        self.config.clone()
+    }
+
+    /// Multi-level POST_ONLY quote ladder for a market that already clears `scan`'s
+    /// filters: `levels` quotes per side spanning from just inside the touch toward
+    /// mid, sized by `profile` ("flat", "front_loaded", or "back_loaded"), replacing
+    /// a single peg with a shape that can capture fills at multiple depths.
+    #[pyo3(name = "scan_ladder", signature = (market_slug, condition_id, best_bid, best_ask, levels, total_size, profile="flat", tick_size=crate::orderbook::DEFAULT_TICK_SIZE))]
+    #[allow(clippy::too_many_arguments)]
+    fn scan_ladder(
+        &self,
+        market_slug: &str,
+        condition_id: &str,
+        best_bid: f64,
+        best_ask: f64,
+        levels: usize,
+        total_size: f64,
+        profile: &str,
+        tick_size: f64,
+    ) -> PyResult<Vec<ladder::QuoteLevel>> {
+        let Some(opp) = self.scan(market_slug, condition_id, best_bid, best_ask) else {
+            return Ok(Vec::new());
+        };
+        let profile = ladder::SizeProfile::from_str_pub(profile)
+            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("unknown size profile: {profile}")))?;
+        Ok(ladder::build_ladder(best_bid, best_ask, levels, total_size, profile, opp.use_post_only, tick_size))
+    }
+
+    /// Decide what to do with a resting order that hasn't filled within its timeout:
+    /// requote from the fresh book, or give up once exit_timeout_count is exhausted.
+    #[pyo3(name = "on_unfilled")]
+    fn on_unfilled(&self, order: lifecycle::RestingOrder, now_ts: u64, best_bid: f64, best_ask: f64) -> lifecycle::UnfilledDecision {
+        lifecycle::on_unfilled(self, &order, now_ts, best_bid, best_ask)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Vulture(min_spread={}bps, max_spread={}bps)", self.config.min_spread_bps, self.config.max_spread_bps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn long_inventory_skews_both_sides_down() {
+        let vulture = Vulture::new();
+        vulture.update_inventory("cond1", 500.0); // long, half of max_position
+        let opp = vulture.scan("btc-15m", "cond1", 0.495, 0.505).unwrap();
+
+        assert!(opp.skew_bps > 0.0);
+        let flat = Vulture::new().scan("btc-15m", "cond2", 0.495, 0.505).unwrap();
+        assert!(opp.quote_bid_price < flat.quote_bid_price);
+        assert!(opp.quote_ask_price < flat.quote_ask_price);
+    }
+
+    #[test]
+    fn short_inventory_skews_both_sides_up() {
+        let vulture = Vulture::new();
+        vulture.update_inventory("cond1", -500.0);
+        let opp = vulture.scan("btc-15m", "cond1", 0.495, 0.505).unwrap();
+
+        assert!(opp.skew_bps < 0.0);
+        let flat = Vulture::new().scan("btc-15m", "cond2", 0.495, 0.505).unwrap();
+        assert!(opp.quote_bid_price > flat.quote_bid_price);
+        assert!(opp.quote_ask_price > flat.quote_ask_price);
+    }
+
+    #[test]
+    fn inventory_skew_saturates_at_max_position() {
+        let vulture = Vulture::new();
+        vulture.update_inventory("cond1", 10_000.0); // far beyond max_position
+        let opp = vulture.scan("btc-15m", "cond1", 0.495, 0.505).unwrap();
+        assert_eq!(opp.skew_bps, vulture.get_config().max_skew_bps);
+    }
+
+    #[test]
+    fn rank_and_filter_drops_markets_below_min_rolling_pnl() {
+        let mut config = VultureConfig::new();
+        config.min_rolling_pnl = 0.0;
+        let vulture = Vulture::with_config(config);
+
+        vulture.record_fill_pnl("good", 100, 5.0);
+        vulture.record_fill_pnl("bad", 100, -5.0);
+
+        let markets = vec![
+            ("good-15m-btc".to_string(), "good".to_string(), 0.495, 0.505),
+            ("bad-15m-btc".to_string(), "bad".to_string(), 0.495, 0.505),
+        ];
+        let ranked = vulture.scan_batch(markets, true, 200);
+
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].condition_id, "good");
+    }
+
+    #[test]
+    fn rank_and_filter_sorts_survivors_by_rolling_pnl_descending() {
+        let vulture = Vulture::new();
+        vulture.record_fill_pnl("low", 100, 1.0);
+        vulture.record_fill_pnl("high", 100, 9.0);
+
+        let markets = vec![
+            ("low-15m-btc".to_string(), "low".to_string(), 0.495, 0.505),
+            ("high-15m-btc".to_string(), "high".to_string(), 0.495, 0.505),
+        ];
+        let ranked = vulture.scan_batch(markets, true, 200);
+
+        assert_eq!(ranked[0].condition_id, "high");
+        assert_eq!(ranked[1].condition_id, "low");
+    }
+
+    #[test]
+    fn rank_and_filter_keeps_markets_with_no_trade_history_at_the_default_threshold() {
+        // A market never recorded in the performance ledger has rolling pnl 0.0,
+        // which must still clear the default min_rolling_pnl of 0.0 (>=, not >).
+        let vulture = Vulture::new();
+        let markets = vec![("untraded-15m-btc".to_string(), "untraded".to_string(), 0.495, 0.505)];
+        let ranked = vulture.scan_batch(markets, true, 200);
+        assert_eq!(ranked.len(), 1);
+    }
+
+    #[test]
+    fn scan_batch_without_rank_and_filter_ignores_performance_entirely() {
+        let vulture = Vulture::new();
+        vulture.record_fill_pnl("bad", 100, -5.0);
+        let markets = vec![("bad-15m-btc".to_string(), "bad".to_string(), 0.495, 0.505)];
+        let opportunities = vulture.scan_batch(markets, false, 200);
+        assert_eq!(opportunities.len(), 1);
+    }
+
+    #[test]
+    fn rolling_performance_ignores_trades_outside_the_window() {
+        let mut config = VultureConfig::new();
+        config.performance_window_secs = 60;
+        let vulture = Vulture::with_config(config);
+
+        vulture.record_fill_pnl("cond1", 0, 10.0);
+        vulture.record_fill_pnl("cond1", 500, 3.0);
+
+        let (pnl, trades) = vulture.rolling_performance("cond1", 500);
+        assert_eq!(trades, 1);
+        assert_eq!(pnl, 3.0);
+    }
+}
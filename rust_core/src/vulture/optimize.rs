@@ -0,0 +1,364 @@
+//! Bayesian auto-tuning of `VultureConfig` against a backtest objective.
+//!
+//! Sequential model-based optimization: a small random-forest surrogate
+//! models the backtest objective over evaluated configs, Expected
+//! Improvement picks the next candidate to try, and the true objective
+//! (net rebate PnL) is backtested to confirm it.
+
+use pyo3::prelude::*;
+
+use super::backtest::{run_backtest, Tick};
+use super::{Vulture, VultureConfig};
+
+/// Number of random warm-up points evaluated before the surrogate kicks in
+const WARMUP_POINTS: usize = 10;
+/// Random candidate configs scored by EI at each optimization round
+const CANDIDATES_PER_ROUND: usize = 2_000;
+/// Exploration slack in Expected Improvement
+const EI_XI: f64 = 0.01;
+/// Trees in the surrogate ensemble
+const FOREST_SIZE: usize = 10;
+/// Max depth of each surrogate tree
+const TREE_MAX_DEPTH: usize = 4;
+/// Minimum points in a tree leaf
+const MIN_LEAF_SAMPLES: usize = 2;
+
+/// Tunable axes, in the fixed order the optimizer works in
+const NUM_PARAMS: usize = 4;
+
+/// Simple xorshift64* PRNG so the optimizer has no external RNG dependency
+struct XorShiftRng {
+    state: u64,
+}
+
+impl XorShiftRng {
+    fn new(seed: u64) -> Self {
+        Self { state: seed | 1 }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Uniform float in [0, 1)
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn uniform(&mut self, lo: f64, hi: f64) -> f64 {
+        lo + self.next_f64() * (hi - lo)
+    }
+
+    fn index(&mut self, n: usize) -> usize {
+        (self.next_u64() as usize) % n
+    }
+}
+
+/// A single CART regression tree node
+enum TreeNode {
+    Leaf { value: f64 },
+    Split { feature: usize, threshold: f64, left: Box<TreeNode>, right: Box<TreeNode> },
+}
+
+impl TreeNode {
+    fn predict(&self, x: &[f64; NUM_PARAMS]) -> f64 {
+        match self {
+            TreeNode::Leaf { value } => *value,
+            TreeNode::Split { feature, threshold, left, right } => {
+                if x[*feature] <= *threshold {
+                    left.predict(x)
+                } else {
+                    right.predict(x)
+                }
+            }
+        }
+    }
+
+    fn fit(rows: &[([f64; NUM_PARAMS], f64)], depth: usize, rng: &mut XorShiftRng) -> Self {
+        let mean = rows.iter().map(|(_, y)| y).sum::<f64>() / rows.len() as f64;
+        if depth >= TREE_MAX_DEPTH || rows.len() < MIN_LEAF_SAMPLES * 2 {
+            return TreeNode::Leaf { value: mean };
+        }
+
+        // Try a handful of random (feature, threshold) splits and keep the best by SSE reduction.
+        let mut best: Option<(usize, f64, f64)> = None; // (feature, threshold, sse)
+        for _ in 0..8 {
+            let feature = rng.index(NUM_PARAMS);
+            let pivot = &rows[rng.index(rows.len())].0;
+            let threshold = pivot[feature];
+
+            let (left, right): (Vec<_>, Vec<_>) = rows.iter().partition(|(x, _)| x[feature] <= threshold);
+            if left.len() < MIN_LEAF_SAMPLES || right.len() < MIN_LEAF_SAMPLES {
+                continue;
+            }
+
+            let sse = sse_of(&left) + sse_of(&right);
+            if best.as_ref().map(|(_, _, best_sse)| sse < *best_sse).unwrap_or(true) {
+                best = Some((feature, threshold, sse));
+            }
+        }
+
+        let Some((feature, threshold, _)) = best else {
+            return TreeNode::Leaf { value: mean };
+        };
+
+        let (left_rows, right_rows): (Vec<_>, Vec<_>) =
+            rows.iter().cloned().partition(|(x, _)| x[feature] <= threshold);
+
+        TreeNode::Split {
+            feature,
+            threshold,
+            left: Box::new(TreeNode::fit(&left_rows, depth + 1, rng)),
+            right: Box::new(TreeNode::fit(&right_rows, depth + 1, rng)),
+        }
+    }
+}
+
+fn sse_of(rows: &[&([f64; NUM_PARAMS], f64)]) -> f64 {
+    let mean = rows.iter().map(|(_, y)| y).sum::<f64>() / rows.len() as f64;
+    rows.iter().map(|(_, y)| (y - mean).powi(2)).sum()
+}
+
+/// A small random-forest surrogate giving both a mean and a cross-tree
+/// variance estimate, used as `μ(x)`/`σ(x)` in Expected Improvement.
+struct RandomForest {
+    trees: Vec<TreeNode>,
+}
+
+impl RandomForest {
+    fn fit(x: &[[f64; NUM_PARAMS]], y: &[f64], rng: &mut XorShiftRng) -> Self {
+        let rows: Vec<([f64; NUM_PARAMS], f64)> = x.iter().zip(y).map(|(x, y)| (*x, *y)).collect();
+        let trees = (0..FOREST_SIZE)
+            .map(|_| {
+                let bootstrap: Vec<_> = (0..rows.len()).map(|_| rows[rng.index(rows.len())].clone()).collect();
+                TreeNode::fit(&bootstrap, 0, rng)
+            })
+            .collect();
+        Self { trees }
+    }
+
+    /// Returns (mean, stddev) of the ensemble's predictions at `x`
+    fn predict(&self, x: &[f64; NUM_PARAMS]) -> (f64, f64) {
+        let preds: Vec<f64> = self.trees.iter().map(|t| t.predict(x)).collect();
+        let mean = preds.iter().sum::<f64>() / preds.len() as f64;
+        let variance = preds.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / preds.len() as f64;
+        (mean, variance.sqrt())
+    }
+}
+
+/// Standard normal CDF via the Abramowitz-Stegun erf approximation
+fn normal_cdf(z: f64) -> f64 {
+    0.5 * (1.0 + erf(z / std::f64::consts::SQRT_2))
+}
+
+/// Standard normal PDF
+fn normal_pdf(z: f64) -> f64 {
+    (-0.5 * z * z).exp() / (2.0 * std::f64::consts::PI).sqrt()
+}
+
+fn erf(x: f64) -> f64 {
+    let sign = if x < 0.0 { -1.0 } else { 1.0 };
+    let x = x.abs();
+    let a1 = 0.254829592;
+    let a2 = -0.284496736;
+    let a3 = 1.421413741;
+    let a4 = -1.453152027;
+    let a5 = 1.061405429;
+    let p = 0.3275911;
+    let t = 1.0 / (1.0 + p * x);
+    let y = 1.0 - (((((a5 * t + a4) * t) + a3) * t + a2) * t + a1) * t * (-x * x).exp();
+    sign * y
+}
+
+/// Expected Improvement of a candidate given the surrogate's (mean, std) and the best objective so far
+fn expected_improvement(mean: f64, std: f64, y_best: f64) -> f64 {
+    if std <= 0.0 {
+        return 0.0;
+    }
+    let z = (mean - y_best - EI_XI) / std;
+    (mean - y_best - EI_XI) * normal_cdf(z) + std * normal_pdf(z)
+}
+
+fn params_to_config(params: &[f64; NUM_PARAMS]) -> VultureConfig {
+    let mut config = VultureConfig::new();
+    config.min_spread_bps = params[0];
+    config.max_spread_bps = params[1];
+    config.min_mid_price = params[2];
+    config.edge_fraction = params[3];
+    config
+}
+
+fn random_params(bounds: &[(f64, f64); NUM_PARAMS], rng: &mut XorShiftRng) -> [f64; NUM_PARAMS] {
+    let mut params = [0.0; NUM_PARAMS];
+    for (i, (lo, hi)) in bounds.iter().enumerate() {
+        params[i] = rng.uniform(*lo, *hi);
+    }
+    params
+}
+
+/// Backtest net rebate PnL for a candidate config, the optimization objective
+fn objective(params: &[f64; NUM_PARAMS], ticks: &[Tick]) -> f64 {
+    let vulture = Vulture::with_config(params_to_config(params));
+    run_backtest(&vulture, ticks).total_rebate_pnl
+}
+
+/// Search `VultureConfig` space to maximize backtested net rebate PnL.
+/// Falls back to pure random search while fewer than `WARMUP_POINTS` have been evaluated.
+/// `n_calls == 0` means no candidate is ever evaluated, so there is nothing
+/// to pick a best index from - return the unoptimized default instead of
+/// indexing the empty `x`/`y` history.
+pub fn optimize(ticks: &[Tick], bounds: [(f64, f64); NUM_PARAMS], n_calls: usize) -> VultureConfig {
+    if n_calls == 0 {
+        return VultureConfig::new();
+    }
+
+    let mut rng = XorShiftRng::new(0x5eed_1234_dead_beef);
+    let mut x: Vec<[f64; NUM_PARAMS]> = Vec::new();
+    let mut y: Vec<f64> = Vec::new();
+
+    let warmup = WARMUP_POINTS.min(n_calls);
+    for _ in 0..warmup {
+        let params = random_params(&bounds, &mut rng);
+        let score = objective(&params, ticks);
+        x.push(params);
+        y.push(score);
+    }
+
+    for _ in warmup..n_calls {
+        let forest = RandomForest::fit(&x, &y, &mut rng);
+        let y_best = y.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+
+        let mut best_candidate = random_params(&bounds, &mut rng);
+        let mut best_ei = f64::NEG_INFINITY;
+        for _ in 0..CANDIDATES_PER_ROUND {
+            let candidate = random_params(&bounds, &mut rng);
+            let (mean, std) = forest.predict(&candidate);
+            let ei = expected_improvement(mean, std, y_best);
+            if ei > best_ei {
+                best_ei = ei;
+                best_candidate = candidate;
+            }
+        }
+
+        let score = objective(&best_candidate, ticks);
+        x.push(best_candidate);
+        y.push(score);
+    }
+
+    let best_idx = y
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(i, _)| i)
+        .unwrap_or(0);
+
+    params_to_config(&x[best_idx])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vulture::backtest::Tick;
+
+    fn sample_ticks() -> Vec<Tick> {
+        (0..20)
+            .map(|i| Tick {
+                timestamp: i * 60,
+                market_slug: "btc-15m".to_string(),
+                condition_id: "cond1".to_string(),
+                best_bid: 0.49 + 0.001 * (i % 2) as f64,
+                best_ask: 0.51 - 0.001 * (i % 2) as f64,
+            })
+            .collect()
+    }
+
+    const BOUNDS: [(f64, f64); NUM_PARAMS] = [(10.0, 200.0), (200.0, 1000.0), (0.01, 0.1), (0.1, 0.9)];
+
+    #[test]
+    fn optimize_with_zero_calls_returns_the_default_config_without_panicking() {
+        let config = optimize(&sample_ticks(), BOUNDS, 0);
+        let default = VultureConfig::new();
+        assert_eq!(config.min_spread_bps, default.min_spread_bps);
+        assert_eq!(config.edge_fraction, default.edge_fraction);
+    }
+
+    #[test]
+    fn optimize_picks_params_within_the_requested_bounds() {
+        let config = optimize(&sample_ticks(), BOUNDS, 5);
+        assert!(config.min_spread_bps >= BOUNDS[0].0 && config.min_spread_bps <= BOUNDS[0].1);
+        assert!(config.max_spread_bps >= BOUNDS[1].0 && config.max_spread_bps <= BOUNDS[1].1);
+        assert!(config.min_mid_price >= BOUNDS[2].0 && config.min_mid_price <= BOUNDS[2].1);
+        assert!(config.edge_fraction >= BOUNDS[3].0 && config.edge_fraction <= BOUNDS[3].1);
+    }
+
+    #[test]
+    fn optimize_runs_past_warmup_into_the_surrogate_phase_without_panicking() {
+        // WARMUP_POINTS is 10; n_calls above that exercises the RandomForest/EI path too.
+        let config = optimize(&sample_ticks(), BOUNDS, WARMUP_POINTS + 3);
+        assert!(config.min_spread_bps >= BOUNDS[0].0 && config.min_spread_bps <= BOUNDS[0].1);
+    }
+
+    #[test]
+    fn expected_improvement_is_zero_for_a_degenerate_surrogate() {
+        assert_eq!(expected_improvement(1.0, 0.0, 0.5), 0.0);
+    }
+
+    #[test]
+    fn expected_improvement_grows_with_predicted_mean_above_best() {
+        let low = expected_improvement(0.5, 0.1, 0.5);
+        let high = expected_improvement(1.0, 0.1, 0.5);
+        assert!(high > low);
+    }
+}
+
+// ============ PyO3 Bindings ============
+
+#[pyclass]
+pub struct VultureOptimizer;
+
+#[pymethods]
+impl VultureOptimizer {
+    #[new]
+    fn new() -> Self {
+        Self
+    }
+
+    /// Optimize VultureConfig against a tick stream. `bounds` is
+    /// `[(min_spread_bps_lo, hi), (max_spread_bps_lo, hi), (min_mid_price_lo, hi), (edge_fraction_lo, hi)]`.
+    fn optimize(
+        &self,
+        ticks: Vec<(u64, String, String, f64, f64)>,
+        bounds: Vec<(f64, f64)>,
+        n_calls: usize,
+    ) -> PyResult<VultureConfig> {
+        if bounds.len() != NUM_PARAMS {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "expected {} (lo, hi) bounds, got {}",
+                NUM_PARAMS,
+                bounds.len()
+            )));
+        }
+        if n_calls == 0 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("n_calls must be at least 1"));
+        }
+        let bounds: [(f64, f64); NUM_PARAMS] = [bounds[0], bounds[1], bounds[2], bounds[3]];
+
+        let ticks: Vec<Tick> = ticks
+            .into_iter()
+            .map(|(timestamp, market_slug, condition_id, best_bid, best_ask)| Tick {
+                timestamp,
+                market_slug,
+                condition_id,
+                best_bid,
+                best_ask,
+            })
+            .collect();
+
+        Ok(optimize(&ticks, bounds, n_calls))
+    }
+}